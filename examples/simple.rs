@@ -27,6 +27,11 @@ fn trace(x_seq: &[char], y_seq: &[char], alignment: &Alignment) {
                 x_vec.push('-');
                 y_vec.push(y_seq[y]);
             }
+            Step::Transpose { x, y } => {
+                print!("~~");
+                x_vec.extend(&x_seq[x..x + 2]);
+                y_vec.extend(&y_seq[y..y + 2]);
+            }
         }
     }
 
@@ -48,9 +53,9 @@ fn trace(x_seq: &[char], y_seq: &[char], alignment: &Alignment) {
     println!("{}", y_str);
 }
 
-fn align<S: Strategy>(str_x: &str, str_y: &str, strategy: S)
+fn align<S>(str_x: &str, str_y: &str, strategy: S)
 where
-    S: Debug,
+    S: Strategy + Debug,
 {
     let sequence_x: Vec<char> = str_x.chars().collect();
     let sequence_y: Vec<char> = str_y.chars().collect();
@@ -0,0 +1,115 @@
+use std::cmp;
+
+// Edit distance with unit costs, keeping two rows of the shorter sequence only.
+pub fn levenshtein<T: PartialEq>(a: &[T], b: &[T]) -> usize {
+    let (long, short) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    let mut previous: Vec<usize> = (0..=short.len()).collect();
+    let mut current = vec![0; short.len() + 1];
+    for (i, x) in long.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, y) in short.iter().enumerate() {
+            let substitution = previous[j] + (x != y) as usize;
+            let gap = cmp::min(previous[j + 1], current[j]) + 1;
+            current[j + 1] = cmp::min(substitution, gap);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[short.len()]
+}
+
+// Edit distance turning `a` into `b`, where inserting an element of `b`,
+// deleting an element of `a` and substituting one may cost differently.
+pub fn weighted_levenshtein<T: PartialEq>(
+    a: &[T],
+    b: &[T],
+    insert: usize,
+    delete: usize,
+    substitute: usize,
+) -> usize {
+    let mut previous: Vec<usize> = (0..=b.len()).map(|j| j * insert).collect();
+    let mut current = vec![0; b.len() + 1];
+    for (i, x) in a.iter().enumerate() {
+        current[0] = (i + 1) * delete;
+        for (j, y) in b.iter().enumerate() {
+            let substitution = previous[j] + if x != y { substitute } else { 0 };
+            let gap = cmp::min(previous[j + 1] + delete, current[j] + insert);
+            current[j + 1] = cmp::min(substitution, gap);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
+// Similarity in [0, 1] based on matches within a window and their transpositions.
+pub fn jaro<T: PartialEq>(a: &[T], b: &[T]) -> f64 {
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+    let window = (cmp::max(a.len(), b.len()) / 2).saturating_sub(1);
+    let mut a_matched = vec![false; a.len()];
+    let mut b_matched = vec![false; b.len()];
+    let mut matches = 0;
+    for (i, x) in a.iter().enumerate() {
+        let start = i.saturating_sub(window);
+        let end = cmp::min(i + window + 1, b.len());
+        for j in start..end {
+            if !b_matched[j] && *x == b[j] {
+                a_matched[i] = true;
+                b_matched[j] = true;
+                matches += 1;
+                break;
+            }
+        }
+    }
+    if matches == 0 {
+        return 0.0;
+    }
+    let a_matches = a.iter().zip(a_matched).filter(|&(_, m)| m).map(|(x, _)| x);
+    let b_matches = b.iter().zip(b_matched).filter(|&(_, m)| m).map(|(y, _)| y);
+    let transpositions = a_matches.zip(b_matches).filter(|(x, y)| x != y).count() / 2;
+    let matches = matches as f64;
+    (matches / a.len() as f64
+        + matches / b.len() as f64
+        + (matches - transpositions as f64) / matches)
+        / 3.0
+}
+
+// Jaro similarity boosted by the length of the common prefix (up to four elements).
+pub fn jaro_winkler<T: PartialEq>(a: &[T], b: &[T]) -> f64 {
+    let similarity = jaro(a, b);
+    let prefix = a.iter().zip(b).take(4).take_while(|(x, y)| x == y).count();
+    similarity + prefix as f64 * 0.1 * (1.0 - similarity)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn levenshtein_works() {
+        assert_eq!(levenshtein(b"kitten", b"sitting"), 3);
+        assert_eq!(levenshtein(b"sitting", b"kitten"), 3);
+        assert_eq!(levenshtein(b"", b"abc"), 3);
+        assert_eq!(levenshtein::<u8>(b"", b""), 0);
+        assert_eq!(levenshtein(b"flaw", b"lawn"), 2);
+    }
+
+    #[test]
+    fn weighted_levenshtein_works() {
+        assert_eq!(weighted_levenshtein(b"kitten", b"sitting", 1, 1, 1), 3);
+        // Dropped symbols are cheap, spurious ones expensive.
+        assert_eq!(weighted_levenshtein(b"abc", b"ac", 5, 1, 5), 1);
+        assert_eq!(weighted_levenshtein(b"ac", b"abc", 5, 1, 5), 5);
+        assert_eq!(weighted_levenshtein(b"abc", b"xbc", 1, 1, 5), 2);
+    }
+
+    #[test]
+    fn jaro_winkler_works() {
+        let close = |a: f64, b: f64| (a - b).abs() < 1e-3;
+        assert!(close(jaro(b"MARTHA", b"MARHTA"), 0.944));
+        assert!(close(jaro_winkler(b"MARTHA", b"MARHTA"), 0.961));
+        assert!(close(jaro_winkler(b"DIXON", b"DICKSONX"), 0.813));
+        assert!(close(jaro(b"ABC", b"XYZ"), 0.0));
+        assert!(close(jaro_winkler::<u8>(b"", b""), 1.0));
+    }
+}
@@ -1,6 +1,12 @@
 // pub mod basic_scoring;
 
+pub mod distance;
+pub mod msa;
 pub mod pair;
+pub mod poa;
+pub mod scoring;
+
+pub use crate::distance::{jaro, jaro_winkler, levenshtein, weighted_levenshtein};
 
 #[test]
 fn it_works() {
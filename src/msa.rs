@@ -0,0 +1,419 @@
+use crate::distance::levenshtein;
+use crate::pair::{
+    Alignment, AlignmentSet, InMemoryAlignmentMatrix, Profile, ProfileError, ScoreError, Step,
+    Strategy,
+};
+
+// `support[i]` counts the sequences agreeing with `sequence[i]`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Consensus<T> {
+    pub sequence: Vec<T>,
+    pub support: Vec<usize>,
+}
+
+impl<T> Consensus<T>
+where
+    T: Clone + PartialEq,
+{
+    // The most frequent entry of each column, skipping columns where gaps
+    // are the most frequent. Ties go to the entry seen first.
+    fn from_columns<'a, I, C>(columns: I) -> Consensus<T>
+    where
+        T: 'a,
+        I: Iterator<Item = C>,
+        C: Iterator<Item = Option<&'a T>>,
+    {
+        let mut consensus = Consensus {
+            sequence: vec![],
+            support: vec![],
+        };
+        for column in columns {
+            let mut counts: Vec<(Option<&T>, usize)> = vec![];
+            for entry in column {
+                match counts.iter_mut().find(|(e, _)| *e == entry) {
+                    Some(count) => count.1 += 1,
+                    None => counts.push((entry, 1)),
+                }
+            }
+            let best = counts.into_iter().rev().max_by_key(|&(_, count)| count);
+            if let Some((Some(symbol), count)) = best {
+                consensus.sequence.push(symbol.clone());
+                consensus.support.push(count);
+            }
+        }
+        consensus
+    }
+
+    // Consensus of alignments of several sequences (y) against a common
+    // reference (x) of length `len`. Inserts relative to the reference are
+    // not part of the consensus.
+    pub fn from_alignments(len: usize, alignments: &[(&Alignment, &[T])]) -> Consensus<T> {
+        let mut columns: Vec<Vec<Option<&T>>> = vec![vec![]; len];
+        for &(alignment, y) in alignments.iter() {
+            for step in alignment.steps() {
+                match step {
+                    Step::Align { x: i, y: j } => columns[i].push(Some(&y[j])),
+                    Step::Delete { x: i } => columns[i].push(None),
+                    _ => (),
+                }
+            }
+        }
+        Consensus::from_columns(columns.into_iter().map(|column| column.into_iter()))
+    }
+}
+
+// Rows in the order of the input sequences, `None` marking a gap. Like
+// those of a `Profile`, all rows need the same length.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MultipleAlignment<T> {
+    rows: Vec<Vec<Option<T>>>,
+}
+
+impl<T> MultipleAlignment<T>
+where
+    T: Clone + PartialEq,
+{
+    pub fn new(rows: Vec<Vec<Option<T>>>) -> Result<MultipleAlignment<T>, ProfileError> {
+        let len = rows.first().map_or(0, |row| row.len());
+        match rows.iter().position(|row| row.len() != len) {
+            Some(index) => Err(ProfileError::RowLength { index }),
+            None => Ok(MultipleAlignment { rows }),
+        }
+    }
+
+    pub fn rows(&self) -> &[Vec<Option<T>>] {
+        &self.rows
+    }
+
+    pub fn depth(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn len(&self) -> usize {
+        self.rows.first().map_or(0, |row| row.len())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn column(&self, index: usize) -> Vec<Option<&T>> {
+        self.rows.iter().map(|row| row[index].as_ref()).collect()
+    }
+
+    pub fn consensus(&self) -> Consensus<T> {
+        Consensus::from_columns(
+            (0..self.len()).map(|i| self.rows.iter().map(move |row| row[i].as_ref())),
+        )
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum GuideTree {
+    Leaf(usize),
+    Node(Box<GuideTree>, Box<GuideTree>),
+}
+
+impl GuideTree {
+    fn leaves(&self) -> Vec<usize> {
+        match *self {
+            GuideTree::Leaf(i) => vec![i],
+            GuideTree::Node(ref left, ref right) => {
+                let mut leaves = left.leaves();
+                leaves.extend(right.leaves());
+                leaves
+            }
+        }
+    }
+}
+
+// Edit distances normalized by the length of the longer sequence.
+pub fn distances<T: PartialEq>(sequences: &[Vec<T>]) -> Vec<Vec<f64>> {
+    let n = sequences.len();
+    let mut distances = vec![vec![0.0; n]; n];
+    for i in 0..n {
+        for j in (i + 1)..n {
+            let len = sequences[i].len().max(sequences[j].len()).max(1);
+            let distance = levenshtein(&sequences[i], &sequences[j]) as f64 / len as f64;
+            distances[i][j] = distance;
+            distances[j][i] = distance;
+        }
+    }
+    distances
+}
+
+// UPGMA clustering of a distance matrix.
+pub fn guide_tree(distances: &[Vec<f64>]) -> Option<GuideTree> {
+    let mut clusters: Vec<(GuideTree, usize)> = (0..distances.len())
+        .map(|i| (GuideTree::Leaf(i), 1))
+        .collect();
+    let mut distances: Vec<Vec<f64>> = distances.to_vec();
+    while clusters.len() > 1 {
+        let mut closest = (0, 1);
+        for i in 0..clusters.len() {
+            for j in (i + 1)..clusters.len() {
+                if distances[i][j] < distances[closest.0][closest.1] {
+                    closest = (i, j);
+                }
+            }
+        }
+        let (i, j) = closest;
+        let (right, right_size) = clusters.remove(j);
+        let (left, left_size) = clusters.remove(i);
+        let size = left_size + right_size;
+        let mut merged: Vec<f64> = (0..distances.len())
+            .filter(|&k| k != i && k != j)
+            .map(|k| {
+                (distances[i][k] * left_size as f64 + distances[j][k] * right_size as f64)
+                    / size as f64
+            })
+            .collect();
+        distances.remove(j);
+        distances.remove(i);
+        for (row, distance) in distances.iter_mut().zip(merged.iter()) {
+            row.remove(j);
+            row.remove(i);
+            row.push(*distance);
+        }
+        merged.push(0.0);
+        distances.push(merged);
+        clusters.push((GuideTree::Node(Box::new(left), Box::new(right)), size));
+    }
+    clusters.pop().map(|(tree, _)| tree)
+}
+
+fn align_tree<T, S>(tree: &GuideTree, sequences: &[Vec<T>], strategy: &S) -> Profile<T>
+where
+    T: Clone + PartialEq,
+    S: Strategy,
+{
+    match *tree {
+        GuideTree::Leaf(i) => Profile::new(&sequences[i]),
+        GuideTree::Node(ref left, ref right) => {
+            let left = align_tree(left, sequences, strategy);
+            let right = align_tree(right, sequences, strategy);
+            let alignment = left.align(&right, strategy);
+            left.merge(&right, &alignment)
+        }
+    }
+}
+
+// Progressively merges profiles along a UPGMA guide tree.
+pub fn progressive<T, S>(sequences: &[Vec<T>], strategy: &S) -> MultipleAlignment<T>
+where
+    T: Clone + PartialEq,
+    S: Strategy,
+{
+    let tree = match guide_tree(&distances(sequences)) {
+        Some(tree) => tree,
+        None => return MultipleAlignment { rows: vec![] },
+    };
+    let profile = align_tree(&tree, sequences, strategy);
+    let mut rows: Vec<(usize, Vec<Option<T>>)> = tree
+        .leaves()
+        .into_iter()
+        .zip(profile.rows().iter().cloned())
+        .collect();
+    rows.sort_by_key(|&(i, _)| i);
+    MultipleAlignment {
+        rows: rows.into_iter().map(|(_, row)| row).collect(),
+    }
+}
+
+// Aligns all sequences against the one with the least summed distance to
+// the others and merges the pairwise alignments, keeping all their gaps.
+// Elements the global alignment of a strategy leaves out at either end,
+// e.g. with free end gaps, become leading and trailing gaps.
+pub fn center_star<T, S>(
+    sequences: &[Vec<T>],
+    strategy: &S,
+) -> Result<MultipleAlignment<T>, ScoreError<()>>
+where
+    T: Clone + PartialEq,
+    S: Strategy,
+{
+    let distances = distances(sequences);
+    let sums = distances.iter().map(|row| row.iter().sum::<f64>());
+    let center = match sums.enumerate().min_by(|(_, a), (_, b)| a.total_cmp(b)) {
+        Some((center, _)) => center,
+        None => return Ok(MultipleAlignment { rows: vec![] }),
+    };
+    let x = &sequences[center];
+
+    // Per row and center position the inserted elements before it and the
+    // element aligned to it. The last position collects trailing inserts.
+    type Layout<T> = Vec<(Vec<T>, Option<T>)>;
+    let layouts = sequences
+        .iter()
+        .map(|y| {
+            let mut layout: Layout<T> = vec![(vec![], None); x.len() + 1];
+            let set: AlignmentSet<InMemoryAlignmentMatrix> =
+                AlignmentSet::new(x.len(), y.len(), strategy.clone(), |i, j| x[i] == y[j])?;
+            let alignment = set.global_alignment();
+            let (origin, end) = (*alignment.origin(), alignment.end());
+            layout[0].0.extend(y[..origin.y].iter().cloned());
+            let mut position = origin.x;
+            for step in alignment.steps() {
+                match step {
+                    Step::Align { x: i, y: j } => {
+                        layout[i].1 = Some(y[j].clone());
+                        position = i + 1;
+                    }
+                    Step::Delete { x: i } => position = i + 1,
+                    Step::Insert { y: j } => layout[position].0.push(y[j].clone()),
+                    // Rows cannot swap elements, so both get aligned in order.
+                    Step::Transpose { x: i, y: j } => {
+                        layout[i].1 = Some(y[j].clone());
+                        layout[i + 1].1 = Some(y[j + 1].clone());
+                        position = i + 2;
+                    }
+                }
+            }
+            layout[x.len()].0.extend(y[end.y..].iter().cloned());
+            Ok(layout)
+        })
+        .collect::<Result<Vec<Layout<T>>, _>>()?;
+
+    let inserts: Vec<usize> = (0..=x.len())
+        .map(|p| {
+            layouts
+                .iter()
+                .map(|layout| layout[p].0.len())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    let rows = layouts
+        .into_iter()
+        .map(|layout| {
+            let mut row = vec![];
+            for (p, (inserted, aligned)) in layout.into_iter().enumerate() {
+                let padding = inserts[p] - inserted.len();
+                row.extend(inserted.into_iter().map(Some));
+                row.extend((0..padding).map(|_| None));
+                if p < x.len() {
+                    row.push(aligned);
+                }
+            }
+            row
+        })
+        .collect();
+    Ok(MultipleAlignment { rows })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pair::{FreeEnds, NeedlemanWunsch};
+
+    fn sequences(strings: &[&str]) -> Vec<Vec<u8>> {
+        strings.iter().map(|s| s.bytes().collect()).collect()
+    }
+
+    #[test]
+    fn guide_tree_works() {
+        let distances = distances(&sequences(&["ACGT", "TTTT", "ACGA"]));
+        let tree = guide_tree(&distances).unwrap();
+        let expected = GuideTree::Node(
+            Box::new(GuideTree::Leaf(1)),
+            Box::new(GuideTree::Node(
+                Box::new(GuideTree::Leaf(0)),
+                Box::new(GuideTree::Leaf(2)),
+            )),
+        );
+        assert_eq!(tree, expected);
+    }
+
+    #[test]
+    fn progressive_works() {
+        let input = sequences(&["GATTACA", "GATACA", "GATTACCA", "GTTACA"]);
+        let strategy = NeedlemanWunsch::new(2, -1, -2, -2);
+        let msa = progressive(&input, &strategy);
+        assert_eq!(msa.depth(), 4);
+        for (row, sequence) in msa.rows().iter().zip(input.iter()) {
+            assert_eq!(row.len(), msa.len());
+            let ungapped: Vec<u8> = row.iter().flatten().cloned().collect();
+            assert_eq!(&ungapped, sequence);
+        }
+        assert_eq!(msa.len(), 8);
+        assert!(msa.column(0).iter().all(|&symbol| symbol == Some(&b'G')));
+    }
+
+    #[test]
+    fn consensus_works() {
+        let msa = MultipleAlignment::new(
+            ["GATTACA", "GA-TACA", "GATTCCA", "-ATTAC-"]
+                .iter()
+                .map(|row| {
+                    row.bytes()
+                        .map(|b| if b == b'-' { None } else { Some(b) })
+                        .collect()
+                })
+                .collect(),
+        )
+        .unwrap();
+        let consensus = msa.consensus();
+        assert_eq!(consensus.sequence, b"GATTACA".to_vec());
+        assert_eq!(consensus.support, vec![3, 4, 3, 4, 3, 4, 3]);
+    }
+
+    #[test]
+    fn uneven_rows_are_rejected() {
+        let rows = vec![vec![Some(b'A')], vec![Some(b'A'), None]];
+        assert_eq!(
+            MultipleAlignment::new(rows).err(),
+            Some(ProfileError::RowLength { index: 1 })
+        );
+    }
+
+    #[test]
+    fn consensus_from_alignments_works() {
+        let reference = b"GATTACA";
+        let reads = sequences(&["GATTACA", "GACTACA", "GACTAA"]);
+        let strategy = NeedlemanWunsch::new(2, -1, -2, -2);
+        let alignments: Vec<Alignment> = reads
+            .iter()
+            .map(|y| {
+                let set: AlignmentSet<InMemoryAlignmentMatrix> =
+                    AlignmentSet::new(reference.len(), y.len(), strategy.clone(), |i, j| {
+                        reference[i] == y[j]
+                    })
+                    .unwrap();
+                set.global_alignment()
+            })
+            .collect();
+        let pairs: Vec<(&Alignment, &[u8])> = alignments
+            .iter()
+            .zip(reads.iter())
+            .map(|(alignment, read)| (alignment, read.as_slice()))
+            .collect();
+        let consensus = Consensus::from_alignments(reference.len(), &pairs);
+        assert_eq!(consensus.sequence, b"GACTACA".to_vec());
+        assert_eq!(consensus.support, vec![3, 3, 2, 3, 3, 2, 3]);
+    }
+
+    #[test]
+    fn center_star_works() {
+        let input = sequences(&["GATTACA", "GATACA", "GATTACCA", "GTTACA"]);
+        let strategy = NeedlemanWunsch::new(2, -1, -2, -2);
+        let msa = center_star(&input, &strategy).unwrap();
+        assert_eq!(msa.depth(), 4);
+        for (row, sequence) in msa.rows().iter().zip(input.iter()) {
+            let ungapped: Vec<u8> = row.iter().flatten().cloned().collect();
+            assert_eq!(&ungapped, sequence);
+        }
+        assert_eq!(msa.len(), 8);
+    }
+
+    #[test]
+    fn center_star_keeps_free_ends() {
+        let input = sequences(&["ACGT", "XXACGT", "ACGTYY"]);
+        let strategy = NeedlemanWunsch::new(2, -1, -2, -2).with_free_ends(FreeEnds::all());
+        let msa = center_star(&input, &strategy).unwrap();
+        for (row, sequence) in msa.rows().iter().zip(input.iter()) {
+            let ungapped: Vec<u8> = row.iter().flatten().cloned().collect();
+            assert_eq!(&ungapped, sequence);
+        }
+        assert_eq!(msa.len(), 8);
+    }
+}
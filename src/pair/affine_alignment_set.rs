@@ -0,0 +1,410 @@
+use std::cmp;
+
+use crate::pair::alignment::Alignment;
+use crate::pair::alignment_matrix::AlignmentMatrix;
+use crate::pair::alignment_set::MIN_SCORE;
+use crate::pair::alignments::Preference;
+use crate::pair::cursor::Cursor;
+use crate::pair::step_mask::StepMask;
+use crate::pair::strategy::{AffineGapModel, AffineStrategy};
+
+// Scores of a cell in each of the three DP states: ending in an alignment (M),
+// a deletion (Ix) or an insertion (Iy).
+#[derive(Copy, Clone)]
+struct Scores {
+    align: isize,
+    delete: isize,
+    insert: isize,
+}
+
+impl Scores {
+    fn max(&self) -> isize {
+        cmp::max(cmp::max(self.align, self.delete), self.insert)
+    }
+
+    fn mask(&self) -> StepMask {
+        StepMask::from_scores(self.align, self.delete, self.insert)
+    }
+}
+
+#[derive(Copy, Clone)]
+struct Highscore {
+    score: isize,
+    // The states reaching the score.
+    states: StepMask,
+    cursor: Cursor,
+}
+
+struct Highscores {
+    local: Highscore,
+    global: Highscore,
+}
+
+impl Highscores {
+    fn update(&mut self, highscore: Highscore) {
+        if highscore.score >= self.local.score {
+            self.local = highscore
+        }
+    }
+}
+
+// The score of a gap of `len`, saturating.
+pub(crate) fn gap_score(open: isize, extend: isize, len: usize) -> isize {
+    let len = isize::try_from(len).unwrap_or(isize::MAX);
+    open.saturating_add(extend.saturating_mul(len))
+}
+
+// Each state has its own matrix, whose step masks point to the state(s)
+// of the preceding cell: `ALIGN` for M, `DELETE` for Ix and `INSERT` for Iy.
+pub struct AffineAlignmentSet<T> {
+    align: T,
+    delete: T,
+    insert: T,
+    highscores: Highscores,
+    preference: Preference,
+}
+
+impl<T, E> AffineAlignmentSet<T>
+where
+    T: AlignmentMatrix<Error = E>,
+{
+    pub fn new<S: AffineStrategy, F>(
+        x_len: usize,
+        y_len: usize,
+        strategy: S,
+        f: F,
+    ) -> Result<AffineAlignmentSet<T>, E>
+    where
+        F: Fn(usize, usize) -> bool,
+    {
+        let (equal, align) = (strategy.match_score(), strategy.mismatch_score());
+        Self::with_scores(
+            x_len,
+            y_len,
+            strategy,
+            |x, y| if f(x, y) { equal } else { align },
+        )
+    }
+
+    // Like `new`, but the score of aligning x[i] with y[j] is given by
+    // `score(i, j)` instead of the match and mismatch scores of the strategy.
+    pub fn with_scores<S: AffineStrategy, F>(
+        x_len: usize,
+        y_len: usize,
+        strategy: S,
+        score: F,
+    ) -> Result<AffineAlignmentSet<T>, E>
+    where
+        F: Fn(usize, usize) -> isize,
+    {
+        let width = x_len + 1;
+        let height = y_len + 1;
+
+        let mut align = T::new(width, height)?;
+        let mut delete = T::new(width, height)?;
+        let mut insert = T::new(width, height)?;
+
+        let (delete_open, delete_extend) = Self::gap_scores(strategy.delete_gaps());
+        let (insert_open, insert_extend) = Self::gap_scores(strategy.insert_gaps());
+
+        Self::prepare_matrices(&mut align, &mut delete, &mut insert);
+        let mut row = Self::prepared_row(width, delete_open, delete_extend);
+        let mut highscores = Self::prepared_highscores();
+
+        for y in 0..y_len {
+            let mut last_diagonal = row[0];
+            row[0] = Scores {
+                align: MIN_SCORE,
+                delete: MIN_SCORE,
+                insert: gap_score(insert_open, insert_extend, y + 1),
+            };
+            for x in 0..x_len {
+                let previous = (last_diagonal, row[x], row[x + 1]);
+                let (masks, scores) = Self::calculate_cell(&strategy, previous, score(x, y));
+                let cursor = Cursor { x: x + 1, y: y + 1 };
+                highscores.update(Highscore {
+                    score: scores.max(),
+                    states: scores.mask(),
+                    cursor,
+                });
+                align.set_at(&cursor, masks.0);
+                delete.set_at(&cursor, masks.1);
+                insert.set_at(&cursor, masks.2);
+                let old_diagonal = row[x + 1];
+                row[x + 1] = scores;
+                last_diagonal = old_diagonal;
+            }
+        }
+
+        highscores.global = Highscore {
+            score: row[x_len].max(),
+            states: row[x_len].mask(),
+            cursor: Cursor { x: x_len, y: y_len },
+        };
+
+        Ok(AffineAlignmentSet {
+            align,
+            delete,
+            insert,
+            highscores,
+            preference: Preference::default(),
+        })
+    }
+
+    // Scores every pair of elements by `substitution`, e.g. to use a
+    // confusion matrix.
+    pub fn with_substitution<S: AffineStrategy, U, F>(
+        x: &[U],
+        y: &[U],
+        strategy: S,
+        substitution: F,
+    ) -> Result<AffineAlignmentSet<T>, E>
+    where
+        F: Fn(&U, &U) -> isize,
+    {
+        Self::with_scores(x.len(), y.len(), strategy, |i, j| {
+            substitution(&x[i], &y[j])
+        })
+    }
+
+    fn gap_scores<G: AffineGapModel>(gaps: &G) -> (isize, isize) {
+        (gaps.open_score(), gaps.extend_score())
+    }
+
+    fn calculate_cell<S: AffineStrategy>(
+        strategy: &S,
+        previous_scores: (Scores, Scores, Scores),
+        substitution: isize,
+    ) -> ((StepMask, StepMask, StepMask), Scores) {
+        let (diagonal, left, up) = previous_scores;
+        let (delete_open, delete_extend) = Self::gap_scores(strategy.delete_gaps());
+        let (insert_open, insert_extend) = Self::gap_scores(strategy.insert_gaps());
+        // Scores saturate like those of `AlignmentSet`, so that opening a
+        // gap from the `MIN_SCORE` of an unreachable state cannot wrap.
+        let delete_opened = delete_open.saturating_add(delete_extend);
+        let insert_opened = insert_open.saturating_add(insert_extend);
+        let delete = Scores {
+            align: left.align.saturating_add(delete_opened),
+            delete: left.delete.saturating_add(delete_extend),
+            insert: left.insert.saturating_add(delete_opened),
+        };
+        let insert = Scores {
+            align: up.align.saturating_add(insert_opened),
+            delete: up.delete.saturating_add(insert_opened),
+            insert: up.insert.saturating_add(insert_extend),
+        };
+        let masks = (diagonal.mask(), delete.mask(), insert.mask());
+        let scores = Scores {
+            align: diagonal.max().saturating_add(substitution),
+            delete: delete.max(),
+            insert: insert.max(),
+        };
+        (masks, scores)
+    }
+
+    fn prepare_matrices(align: &mut T, delete: &mut T, insert: &mut T) {
+        let origin = Cursor { x: 0, y: 0 };
+        align.set_at(&origin, StepMask::STOP);
+        delete.set_at(&origin, StepMask::STOP);
+        insert.set_at(&origin, StepMask::STOP);
+        for y in 1..align.height() {
+            let cursor = Cursor { x: 0, y };
+            let mask = if y == 1 {
+                StepMask::ALIGN
+            } else {
+                StepMask::INSERT
+            };
+            align.set_at(&cursor, StepMask::STOP);
+            delete.set_at(&cursor, StepMask::STOP);
+            insert.set_at(&cursor, mask);
+        }
+        for x in 1..align.width() {
+            let cursor = Cursor { x, y: 0 };
+            let mask = if x == 1 {
+                StepMask::ALIGN
+            } else {
+                StepMask::DELETE
+            };
+            align.set_at(&cursor, StepMask::STOP);
+            delete.set_at(&cursor, mask);
+            insert.set_at(&cursor, StepMask::STOP);
+        }
+    }
+
+    fn prepared_row(width: usize, open: isize, extend: isize) -> Vec<Scores> {
+        (0..width)
+            .map(|i| match i {
+                0 => Scores {
+                    align: 0,
+                    delete: MIN_SCORE,
+                    insert: MIN_SCORE,
+                },
+                _ => Scores {
+                    align: MIN_SCORE,
+                    delete: gap_score(open, extend, i),
+                    insert: MIN_SCORE,
+                },
+            })
+            .collect()
+    }
+
+    fn prepared_highscores() -> Highscores {
+        let highscore = Highscore {
+            score: 0,
+            states: StepMask::ALIGN,
+            cursor: Cursor { x: 0, y: 0 },
+        };
+        Highscores {
+            local: highscore,
+            global: highscore,
+        }
+    }
+
+    fn matrix(&self, state: StepMask) -> &T {
+        match state {
+            StepMask::ALIGN => &self.align,
+            StepMask::DELETE => &self.delete,
+            _ => &self.insert,
+        }
+    }
+
+    // Of co-optimal states the one whose step comes first in `preference`
+    // gets followed, `Preference::GAPS_RIGHT` by default.
+    pub fn with_preference(mut self, preference: Preference) -> AffineAlignmentSet<T> {
+        self.preference = preference;
+        self
+    }
+
+    fn alignment(&self, highscore: &Highscore) -> Alignment {
+        let zero = Cursor { x: 0, y: 0 };
+        let mut cursor = highscore.cursor;
+        let mut state = self.preference.first(highscore.states);
+        let mut steps = vec![];
+        while let Some(current) = state {
+            if cursor == zero {
+                break;
+            }
+            let mask = self.matrix(current).at(&cursor);
+            if mask == StepMask::STOP {
+                break;
+            }
+            steps.push(current);
+            cursor.apply_backwards_step(current);
+            state = self.preference.first(mask);
+        }
+        steps.reverse();
+        Alignment::new(cursor, steps, highscore.score)
+    }
+
+    pub fn local_score(&self) -> isize {
+        self.highscores.local.score
+    }
+
+    pub fn global_score(&self) -> isize {
+        self.highscores.global.score
+    }
+
+    pub fn local_max(&self) -> &Cursor {
+        &self.highscores.local.cursor
+    }
+
+    pub fn global_max(&self) -> &Cursor {
+        &self.highscores.global.cursor
+    }
+
+    pub fn local_alignment(&self) -> Alignment {
+        self.alignment(&self.highscores.local)
+    }
+
+    pub fn global_alignment(&self) -> Alignment {
+        self.alignment(&self.highscores.global)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pair::alignment_matrix::in_memory::AlignmentMatrix as InMemoryAlignmentMatrix;
+    use crate::pair::gap_models::AffineGaps;
+    use crate::pair::gotoh::Gotoh;
+
+    fn alignment_set(
+        x: &str,
+        y: &str,
+        strategy: Gotoh,
+    ) -> AffineAlignmentSet<InMemoryAlignmentMatrix> {
+        let x: Vec<char> = x.chars().collect();
+        let y: Vec<char> = y.chars().collect();
+        AffineAlignmentSet::new(x.len(), y.len(), strategy, |i, j| x[i] == y[j]).unwrap()
+    }
+
+    fn masks(alignment: &Alignment) -> Vec<StepMask> {
+        alignment.steps().map(|step| step.mask()).collect()
+    }
+
+    #[test]
+    fn single_gap_works() {
+        let set = alignment_set("ABCD", "AD", Gotoh::new(1, -1, -2, -1));
+        let alignment = set.global_alignment();
+        assert_eq!(set.global_score(), -2);
+        assert_eq!(
+            masks(&alignment),
+            vec![
+                StepMask::ALIGN,
+                StepMask::DELETE,
+                StepMask::DELETE,
+                StepMask::ALIGN
+            ]
+        );
+    }
+
+    #[test]
+    fn prefers_long_gaps() {
+        let set = alignment_set("AAXXXXAA", "AAAA", Gotoh::new(2, -1, -5, -1));
+        let alignment = set.global_alignment();
+        let gap_openings = alignment.runs().filter(|run| run.mask() != StepMask::ALIGN);
+        assert_eq!(gap_openings.count(), 1);
+        assert_eq!(set.global_score(), 8 - 5 - 4);
+    }
+
+    #[test]
+    fn empty_works() {
+        let set = alignment_set("", "ABC", Gotoh::new(1, -1, -2, -1));
+        let alignment = set.global_alignment();
+        assert_eq!(set.global_score(), -5);
+        assert_eq!(masks(&alignment), vec![StepMask::INSERT; 3]);
+        assert_eq!(alignment.origin(), &Cursor { x: 0, y: 0 });
+    }
+
+    #[test]
+    fn asymmetric_gaps_work() {
+        let strategy = Gotoh::new(2, -1, -2, -1).with_insert_gaps(AffineGaps {
+            open: -6,
+            extend: -3,
+        });
+        let set = alignment_set("ABXCD", "ABCD", strategy.clone());
+        assert_eq!(set.global_score(), 8 - 3);
+        let set = alignment_set("ABCD", "ABXCD", strategy);
+        assert_eq!(set.global_score(), 8 - 9);
+    }
+
+    #[test]
+    fn preferences_work() {
+        let set = alignment_set("ACCGT", "ACGT", Gotoh::new(1, -1, -2, -1));
+        let gap = |alignment: Alignment| {
+            alignment
+                .steps()
+                .position(|step| step.mask() == StepMask::DELETE)
+        };
+        assert_eq!(gap(set.global_alignment()), Some(2));
+        let set = set.with_preference(Preference::GAPS_LEFT);
+        assert_eq!(gap(set.global_alignment()), Some(1));
+    }
+
+    #[test]
+    fn huge_gap_scores_saturate() {
+        let gap = -(isize::MAX / 2);
+        let set = alignment_set("ACGT", "ACGT", Gotoh::new(1, -1, gap, gap));
+        assert_eq!(set.global_score(), 4);
+    }
+}
@@ -1,8 +1,21 @@
+use std::ops::Range;
+
 use crate::pair::cursor::Cursor;
+use crate::pair::edit::{self, Edit};
+use crate::pair::pairs::Pairs;
 use crate::pair::runs::Runs;
+use crate::pair::step::Step;
 use crate::pair::step_mask::StepMask;
 use crate::pair::steps::Steps;
 
+// Which of the aligned sequences is the reference of a CIGAR string, i.e.
+// the one whose elements missing from the other count as deletions.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Reference {
+    X,
+    Y,
+}
+
 #[derive(Debug)]
 pub struct Alignment {
     origin: Cursor,
@@ -23,6 +36,14 @@ impl Alignment {
         &self.origin
     }
 
+    pub fn end(&self) -> Cursor {
+        let mut cursor = self.origin;
+        for step_mask in self.steps.iter() {
+            cursor.apply_forwards_step(*step_mask);
+        }
+        cursor
+    }
+
     pub fn score(&self) -> isize {
         self.score
     }
@@ -35,13 +56,191 @@ impl Alignment {
         self.steps.is_empty()
     }
 
-    pub fn steps(&self) -> Steps {
+    pub fn steps(&self) -> Steps<'_> {
         Steps::new(self.steps.iter(), self.origin)
     }
 
     pub fn runs(&self) -> Runs<'_> {
         Runs::new(self.steps().peekable())
     }
+
+    pub fn pairs(&self) -> Pairs<'_> {
+        Pairs::new(self.steps())
+    }
+
+    // The aligned regions of x and y with `None` for gaps, column by column.
+    pub fn gapped<T: Clone>(&self, x: &[T], y: &[T]) -> (Vec<Option<T>>, Vec<Option<T>>) {
+        self.pairs()
+            .map(|(i, j)| (i.map(|i| x[i].clone()), j.map(|j| y[j].clone())))
+            .unzip()
+    }
+
+    // The aligned regions of x and y, from the origin to the end.
+    pub fn x_range(&self) -> Range<usize> {
+        self.origin.x..self.end().x
+    }
+
+    pub fn y_range(&self) -> Range<usize> {
+        self.origin.y..self.end().y
+    }
+
+    // The share of x, respectively y, of length `len` within the aligned
+    // region, e.g. to filter out short local hits.
+    pub fn x_coverage(&self, len: usize) -> f64 {
+        Self::coverage(self.x_range(), len)
+    }
+
+    pub fn y_coverage(&self, len: usize) -> f64 {
+        Self::coverage(self.y_range(), len)
+    }
+
+    fn coverage(range: Range<usize>, len: usize) -> f64 {
+        if len == 0 {
+            return 0.0;
+        }
+        range.len() as f64 / len as f64
+    }
+
+    // The number of columns of the alignment, i.e. of aligned pairs and
+    // gaps, with transpositions making up two columns.
+    pub fn aligned_length(&self) -> usize {
+        self.steps
+            .iter()
+            .map(|&step| if step == StepMask::TRANSPOSE { 2 } else { 1 })
+            .sum()
+    }
+
+    // The number of gap columns, respectively of runs of them.
+    pub fn gaps(&self) -> usize {
+        self.steps
+            .iter()
+            .filter(|&&step| Self::is_gap(step))
+            .count()
+    }
+
+    pub fn gap_openings(&self) -> usize {
+        self.runs().filter(|run| Self::is_gap(run.mask())).count()
+    }
+
+    // The share of columns aligning elements for which `f(x, y)` holds.
+    pub fn identity<F>(&self, f: F) -> f64
+    where
+        F: Fn(usize, usize) -> bool,
+    {
+        self.share(f)
+    }
+
+    // The share of columns aligning elements scoring positively, e.g. by a
+    // substitution matrix.
+    pub fn similarity<F>(&self, score: F) -> f64
+    where
+        F: Fn(usize, usize) -> isize,
+    {
+        self.share(|x, y| score(x, y) > 0)
+    }
+
+    fn share<F>(&self, f: F) -> f64
+    where
+        F: Fn(usize, usize) -> bool,
+    {
+        let columns = self.aligned_length();
+        if columns == 0 {
+            return 0.0;
+        }
+        let mut count = 0;
+        for step in self.steps() {
+            match step {
+                Step::Align { x, y } => count += f(x, y) as usize,
+                Step::Transpose { x, y } => {
+                    count += f(x, y) as usize + f(x + 1, y + 1) as usize;
+                }
+                Step::Delete { .. } | Step::Insert { .. } => {}
+            }
+        }
+        count as f64 / columns as f64
+    }
+
+    fn is_gap(step: StepMask) -> bool {
+        step == StepMask::DELETE || step == StepMask::INSERT
+    }
+
+    // The edits turning the aligned region of x into that of y, merged into
+    // runs, with aligned elements for which `f(x, y)` holds kept as is.
+    pub fn edits<F>(&self, f: F) -> Vec<Edit>
+    where
+        F: Fn(usize, usize) -> bool,
+    {
+        let mut pos = self.origin.x;
+        let edits = self.pairs().filter_map(|pair| {
+            let edit = match pair {
+                (Some(x), Some(y)) if f(x, y) => None,
+                (Some(x), Some(y)) => Some(Edit::Substitute {
+                    pos: x,
+                    len: 1,
+                    source: y,
+                }),
+                (Some(x), None) => Some(Edit::Delete { pos: x, len: 1 }),
+                (None, Some(y)) => Some(Edit::Insert {
+                    pos,
+                    len: 1,
+                    source: y,
+                }),
+                (None, None) => None,
+            };
+            if let (Some(x), _) = pair {
+                pos = x + 1;
+            }
+            edit
+        });
+        edit::merged(edits)
+    }
+
+    // CIGAR string of `M`, `I` and `D` operations. Transpositions count as
+    // two `M`s.
+    pub fn to_cigar(&self, reference: Reference) -> String {
+        self.cigar(reference, |_, _| 'M')
+    }
+
+    // Like `to_cigar`, but with `=` for aligned elements for which `f(x, y)`
+    // holds and `X` for others, instead of `M`.
+    pub fn to_extended_cigar<F>(&self, reference: Reference, f: F) -> String
+    where
+        F: Fn(usize, usize) -> bool,
+    {
+        self.cigar(reference, |x, y| if f(x, y) { '=' } else { 'X' })
+    }
+
+    fn cigar<F>(&self, reference: Reference, aligned: F) -> String
+    where
+        F: Fn(usize, usize) -> char,
+    {
+        let (delete, insert) = match reference {
+            Reference::X => ('D', 'I'),
+            Reference::Y => ('I', 'D'),
+        };
+        let mut operations = vec![];
+        for step in self.steps() {
+            match step {
+                Step::Align { x, y } => operations.push(aligned(x, y)),
+                Step::Delete { .. } => operations.push(delete),
+                Step::Insert { .. } => operations.push(insert),
+                Step::Transpose { x, y } => {
+                    operations.push(aligned(x, y));
+                    operations.push(aligned(x + 1, y + 1));
+                }
+            }
+        }
+        let mut cigar = String::new();
+        let mut operations = operations.into_iter().peekable();
+        while let Some(operation) = operations.next() {
+            let mut count = 1;
+            while operations.next_if_eq(&operation).is_some() {
+                count += 1;
+            }
+            cigar.push_str(&format!("{}{}", count, operation));
+        }
+        cigar
+    }
 }
 
 #[cfg(test)]
@@ -78,8 +277,113 @@ mod tests {
         }
     }
 
+    #[test]
+    fn end_works() {
+        assert_eq!(alignment().end(), Cursor { x: 6, y: 6 });
+    }
+
     #[test]
     fn score_works() {
         assert_eq!(alignment().score(), score());
     }
+
+    #[test]
+    fn cigar_works() {
+        let steps = vec![
+            StepMask::ALIGN,
+            StepMask::ALIGN,
+            StepMask::DELETE,
+            StepMask::ALIGN,
+            StepMask::INSERT,
+            StepMask::INSERT,
+        ];
+        let alignment = Alignment::new(Cursor { x: 0, y: 0 }, steps, 0);
+        assert_eq!(alignment.to_cigar(Reference::X), "2M1D1M2I");
+        assert_eq!(alignment.to_cigar(Reference::Y), "2M1I1M2D");
+        let cigar = alignment.to_extended_cigar(Reference::X, |x, _| x != 1);
+        assert_eq!(cigar, "1=1X1D1=2I");
+    }
+
+    #[test]
+    fn statistics_work() {
+        let x: Vec<char> = "ACGTTACG".chars().collect();
+        let y: Vec<char> = "ACTTAGGGG".chars().collect();
+        let steps = vec![
+            StepMask::ALIGN,
+            StepMask::ALIGN,
+            StepMask::DELETE,
+            StepMask::ALIGN,
+            StepMask::ALIGN,
+            StepMask::ALIGN,
+            StepMask::ALIGN,
+            StepMask::ALIGN,
+            StepMask::INSERT,
+            StepMask::INSERT,
+        ];
+        let alignment = Alignment::new(Cursor { x: 0, y: 0 }, steps, 0);
+        assert_eq!(alignment.aligned_length(), 10);
+        assert_eq!(alignment.gaps(), 3);
+        assert_eq!(alignment.gap_openings(), 2);
+        assert_eq!(alignment.identity(|i, j| x[i] == y[j]), 0.6);
+        let similar = |a: char, b: char| a == b || (a == 'C') == (b == 'G');
+        let score = |i: usize, j: usize| if similar(x[i], y[j]) { 1 } else { -1 };
+        assert_eq!(alignment.similarity(score), 0.7);
+    }
+
+    #[test]
+    fn coverage_works() {
+        let steps = vec![StepMask::ALIGN, StepMask::DELETE, StepMask::ALIGN];
+        let alignment = Alignment::new(Cursor { x: 2, y: 1 }, steps, 0);
+        assert_eq!(alignment.x_range(), 2..5);
+        assert_eq!(alignment.y_range(), 1..3);
+        assert_eq!(alignment.x_coverage(10), 0.3);
+        assert_eq!(alignment.y_coverage(4), 0.5);
+        assert_eq!(alignment.y_coverage(0), 0.0);
+    }
+
+    #[test]
+    fn edits_work() {
+        let x: Vec<char> = "ACGTTACG".chars().collect();
+        let y: Vec<char> = "ACTTAGGAA".chars().collect();
+        let steps = vec![
+            StepMask::ALIGN,
+            StepMask::ALIGN,
+            StepMask::DELETE,
+            StepMask::ALIGN,
+            StepMask::ALIGN,
+            StepMask::ALIGN,
+            StepMask::ALIGN,
+            StepMask::ALIGN,
+            StepMask::INSERT,
+            StepMask::INSERT,
+        ];
+        let alignment = Alignment::new(Cursor { x: 0, y: 0 }, steps, 0);
+        let edits = alignment.edits(|i, j| x[i] == y[j]);
+        assert_eq!(
+            edits,
+            vec![
+                Edit::Delete { pos: 2, len: 1 },
+                Edit::Substitute {
+                    pos: 6,
+                    len: 1,
+                    source: 5
+                },
+                Edit::Insert {
+                    pos: 8,
+                    len: 2,
+                    source: 7
+                },
+            ]
+        );
+        assert_eq!(edit::apply(&edits, &x, &y), y);
+    }
+
+    #[test]
+    fn gapped_works() {
+        let steps = vec![StepMask::ALIGN, StepMask::DELETE, StepMask::INSERT];
+        let alignment = Alignment::new(Cursor { x: 1, y: 0 }, steps, 0);
+        let (x, y) = alignment.gapped(&[1, 2, 3], &[4, 5]);
+        assert_eq!(x, vec![Some(2), Some(3), None]);
+        assert_eq!(y, vec![Some(4), None, Some(5)]);
+    }
 }
@@ -0,0 +1,139 @@
+use std::fmt;
+
+use crate::pair::cursor::Cursor;
+use crate::pair::step_mask::StepMask;
+
+use super::AlignmentMatrix as AlignmentMatrixTrait;
+
+// Stores the cells of each anti-diagonal, i.e. those with the same `x + y`,
+// next to each other, ordered by x. Cells computed together by wavefront or
+// SIMD fills are thus contiguous.
+pub struct AlignmentMatrix {
+    width: usize,
+    height: usize,
+    // The offset of the first cell of each anti-diagonal.
+    starts: Vec<usize>,
+    buffer: Vec<StepMask>,
+}
+
+impl AlignmentMatrix {
+    fn offset(&self, cursor: &Cursor) -> usize {
+        let diagonal = cursor.x + cursor.y;
+        let first = diagonal.saturating_sub(self.height - 1);
+        self.starts[diagonal] + cursor.x - first
+    }
+
+    // The cells with `x + y == diagonal`, by increasing x.
+    pub fn diagonal(&self, diagonal: usize) -> &[StepMask] {
+        let end = self
+            .starts
+            .get(diagonal + 1)
+            .cloned()
+            .unwrap_or(self.buffer.len());
+        &self.buffer[self.starts[diagonal]..end]
+    }
+}
+
+impl AlignmentMatrixTrait for AlignmentMatrix {
+    type Error = ();
+
+    fn new(width: usize, height: usize) -> Result<Self, Self::Error> {
+        let diagonals = if width == 0 || height == 0 {
+            0
+        } else {
+            width + height - 1
+        };
+        let mut starts = Vec::with_capacity(diagonals);
+        let mut offset = 0;
+        for diagonal in 0..diagonals {
+            starts.push(offset);
+            let first = diagonal.saturating_sub(height - 1);
+            let last = diagonal.min(width - 1);
+            offset += last + 1 - first;
+        }
+        Ok(Self {
+            width,
+            height,
+            starts,
+            buffer: vec![StepMask::STOP; width * height],
+        })
+    }
+
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn at(&self, cursor: &Cursor) -> StepMask {
+        self.buffer[self.offset(cursor)]
+    }
+
+    fn set_at(&mut self, cursor: &Cursor, step_mask: StepMask) {
+        let offset = self.offset(cursor);
+        self.buffer[offset] = step_mask;
+    }
+}
+
+impl fmt::Debug for AlignmentMatrix {
+    fn fmt(&self, form: &mut fmt::Formatter) -> fmt::Result {
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                let cursor = Cursor { x, y };
+                let _ = write!(form, "{:?}\t", self.at(&cursor));
+            }
+            let _ = writeln!(form);
+        }
+        writeln!(form)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Writes a mask per cell which tells apart its neighbours on the same
+    // anti-diagonal.
+    fn filled(width: usize, height: usize) -> AlignmentMatrix {
+        let masks = [StepMask::ALIGN, StepMask::DELETE, StepMask::INSERT];
+        let mut matrix = AlignmentMatrix::new(width, height).unwrap();
+        for y in 0..height {
+            for x in 0..width {
+                matrix.set_at(&Cursor { x, y }, masks[x % 3]);
+            }
+        }
+        matrix
+    }
+
+    #[test]
+    fn diagonals_are_contiguous() {
+        for (width, height) in [(5, 3), (3, 5), (4, 4), (1, 4), (4, 1)] {
+            let matrix = filled(width, height);
+            let mut cells = 0;
+            for diagonal in 0..width + height - 1 {
+                let expected: Vec<StepMask> = (0..width)
+                    .filter(|&x| x <= diagonal && diagonal - x < height)
+                    .map(|x| matrix.at(&Cursor { x, y: diagonal - x }))
+                    .collect();
+                assert_eq!(matrix.diagonal(diagonal), &expected[..]);
+                // Each diagonal starts right where the previous one ends.
+                assert_eq!(matrix.starts[diagonal], cells);
+                cells += expected.len();
+            }
+            assert_eq!(cells, matrix.buffer.len());
+        }
+    }
+
+    #[test]
+    fn corners_are_single_cells() {
+        let matrix = filled(5, 3);
+        assert_eq!(matrix.diagonal(0), &[StepMask::ALIGN]);
+        assert_eq!(matrix.diagonal(6), &[StepMask::DELETE]);
+        assert_eq!(
+            matrix.diagonal(2),
+            &[StepMask::ALIGN, StepMask::DELETE, StepMask::INSERT]
+        );
+    }
+}
@@ -0,0 +1,112 @@
+use std::{cmp, fmt};
+
+use crate::pair::cursor::Cursor;
+use crate::pair::step_mask::StepMask;
+
+use super::AlignmentMatrix as AlignmentMatrixTrait;
+
+// Stores only the cells within `bandwidth` of the main diagonal, i.e.
+// `2 * bandwidth + 1` cells per row. Cells outside of the band read as
+// `STOP`, writes to them get dropped.
+pub struct AlignmentMatrix {
+    width: usize,
+    height: usize,
+    bandwidth: usize,
+    buffer: Vec<StepMask>,
+}
+
+impl AlignmentMatrix {
+    fn offset(&self, cursor: &Cursor) -> Option<usize> {
+        if cursor.x.abs_diff(cursor.y) > self.bandwidth
+            || cursor.x >= self.width
+            || cursor.y >= self.height
+        {
+            return None;
+        }
+        let row = 2 * self.bandwidth + 1;
+        Some(cursor.y * row + cursor.x + self.bandwidth - cursor.y)
+    }
+}
+
+impl AlignmentMatrixTrait for AlignmentMatrix {
+    type Error = ();
+
+    fn new(width: usize, height: usize) -> Result<Self, Self::Error> {
+        Self::with_bandwidth(width, height, cmp::max(width, height))
+    }
+
+    fn with_bandwidth(width: usize, height: usize, bandwidth: usize) -> Result<Self, Self::Error> {
+        let bandwidth = cmp::min(bandwidth, cmp::max(width, height));
+        let buffer = vec![StepMask::STOP; height * (2 * bandwidth + 1)];
+        Ok(Self {
+            width,
+            height,
+            bandwidth,
+            buffer,
+        })
+    }
+
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn at(&self, cursor: &Cursor) -> StepMask {
+        match self.offset(cursor) {
+            Some(offset) => self.buffer[offset],
+            None => StepMask::STOP,
+        }
+    }
+
+    fn set_at(&mut self, cursor: &Cursor, step_mask: StepMask) {
+        if let Some(offset) = self.offset(cursor) {
+            self.buffer[offset] = step_mask;
+        }
+    }
+}
+
+impl fmt::Debug for AlignmentMatrix {
+    fn fmt(&self, form: &mut fmt::Formatter) -> fmt::Result {
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                let cursor = Cursor { x, y };
+                let _ = write!(form, "{:?}\t", self.at(&cursor));
+            }
+            let _ = writeln!(form);
+        }
+        writeln!(form)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pair::alignment_matrix::in_memory::AlignmentMatrix as InMemoryAlignmentMatrix;
+    use crate::pair::alignment_set::AlignmentSet;
+    use crate::pair::needleman_wunsch::NeedlemanWunsch;
+
+    #[test]
+    fn band_is_stored() {
+        let x: Vec<char> = "The quick brown fox jumps over the lazy dog."
+            .chars()
+            .collect();
+        let y: Vec<char> = "The quick brown dog jumps over the very lazy fox."
+            .chars()
+            .collect();
+        let strategy = NeedlemanWunsch::new(1, -1, -1, -1).with_bandwidth(3);
+        let banded: AlignmentSet<AlignmentMatrix> =
+            AlignmentSet::new(x.len(), y.len(), strategy.clone(), |i, j| x[i] == y[j]).unwrap();
+        let dense: AlignmentSet<InMemoryAlignmentMatrix> =
+            AlignmentSet::new(x.len(), y.len(), strategy, |i, j| x[i] == y[j]).unwrap();
+        assert_eq!(banded.global_score(), dense.global_score());
+        let banded_steps: Vec<_> = banded.global_alignment().steps().collect();
+        let dense_steps: Vec<_> = dense.global_alignment().steps().collect();
+        assert_eq!(banded_steps, dense_steps);
+        // The band got widened to the length difference of five.
+        assert_eq!(banded.matrix().buffer.len(), (y.len() + 1) * 11);
+        assert_eq!(banded.matrix().at(&Cursor { x: 0, y: 20 }), StepMask::STOP);
+    }
+}
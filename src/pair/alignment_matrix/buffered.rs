@@ -0,0 +1,153 @@
+use std::fmt;
+
+use crate::pair::cursor::Cursor;
+use crate::pair::step_mask::StepMask;
+
+use super::AlignmentMatrix as AlignmentMatrixTrait;
+
+// Storage for the cells of a matrix, e.g. in an arena, huge pages or pinned
+// memory. `allocate` needs to return at least `len` cells.
+pub trait Buffer: AsRef<[StepMask]> + AsMut<[StepMask]> + Sized {
+    type Error;
+
+    fn allocate(len: usize) -> Result<Self, Self::Error>;
+}
+
+impl Buffer for Vec<StepMask> {
+    type Error = ();
+
+    fn allocate(len: usize) -> Result<Self, Self::Error> {
+        Ok(vec![StepMask::STOP; len])
+    }
+}
+
+impl Buffer for Box<[StepMask]> {
+    type Error = ();
+
+    fn allocate(len: usize) -> Result<Self, Self::Error> {
+        Ok(vec![StepMask::STOP; len].into_boxed_slice())
+    }
+}
+
+// A dense matrix like `InMemoryAlignmentMatrix`, in a buffer provided by
+// the caller.
+pub struct AlignmentMatrix<B> {
+    width: usize,
+    height: usize,
+    buffer: B,
+}
+
+impl<B> AlignmentMatrix<B>
+where
+    B: AsRef<[StepMask]> + AsMut<[StepMask]>,
+{
+    // Hands the buffer back if it is too small for the matrix.
+    pub fn from_buffer(width: usize, height: usize, buffer: B) -> Result<Self, B> {
+        if buffer.as_ref().len() < width * height {
+            return Err(buffer);
+        }
+        Ok(Self {
+            width,
+            height,
+            buffer,
+        })
+    }
+
+    pub fn into_buffer(self) -> B {
+        self.buffer
+    }
+
+    fn offset(&self, cursor: &Cursor) -> usize {
+        cursor.x + (cursor.y * self.width)
+    }
+}
+
+impl<B> AlignmentMatrixTrait for AlignmentMatrix<B>
+where
+    B: Buffer,
+{
+    type Error = B::Error;
+
+    fn new(width: usize, height: usize) -> Result<Self, Self::Error> {
+        let buffer = B::allocate(width * height)?;
+        Ok(Self {
+            width,
+            height,
+            buffer,
+        })
+    }
+
+    // Keeps the buffer if it is big enough.
+    fn resize(&mut self, width: usize, height: usize) -> Result<(), Self::Error> {
+        let len = width * height;
+        match self.buffer.as_mut().get_mut(..len) {
+            Some(cells) => cells.fill(StepMask::STOP),
+            None => self.buffer = B::allocate(len)?,
+        }
+        self.width = width;
+        self.height = height;
+        Ok(())
+    }
+
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn at(&self, cursor: &Cursor) -> StepMask {
+        self.buffer.as_ref()[self.offset(cursor)]
+    }
+
+    fn set_at(&mut self, cursor: &Cursor, step_mask: StepMask) {
+        let offset = self.offset(cursor);
+        self.buffer.as_mut()[offset] = step_mask;
+    }
+}
+
+impl<B> fmt::Debug for AlignmentMatrix<B>
+where
+    B: Buffer,
+{
+    fn fmt(&self, form: &mut fmt::Formatter) -> fmt::Result {
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                let cursor = Cursor { x, y };
+                let _ = write!(form, "{:?}\t", self.at(&cursor));
+            }
+            let _ = writeln!(form);
+        }
+        writeln!(form)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pair::alignment_set::AlignmentSet;
+    use crate::pair::needleman_wunsch::NeedlemanWunsch;
+
+    #[test]
+    fn buffers_work() {
+        let x = b"ACGTACGT";
+        let y = b"ACGACGT";
+        let strategy = NeedlemanWunsch::new(1, -1, -1, -1);
+        let set: AlignmentSet<AlignmentMatrix<Box<[StepMask]>>> =
+            AlignmentSet::new(x.len(), y.len(), strategy.clone(), |i, j| x[i] == y[j]).unwrap();
+        assert_eq!(set.global_score(), 6);
+
+        let buffer = vec![StepMask::STOP; 10];
+        let buffer = AlignmentMatrix::from_buffer(4, 3, buffer).unwrap_err();
+        let matrix = AlignmentMatrix::from_buffer(2, 5, buffer).unwrap();
+        let set = AlignmentSet::builder(2, 1, strategy)
+            .with_matrix(matrix)
+            .build(|i, j| x[i] == y[j])
+            .unwrap();
+        assert_eq!(set.global_score(), 0);
+        let buffer = set.into_matrix().into_buffer();
+        assert_eq!(buffer.len(), 10);
+        assert_eq!(buffer[4], StepMask::ALIGN);
+    }
+}
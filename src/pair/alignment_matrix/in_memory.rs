@@ -22,7 +22,7 @@ impl AlignmentMatrixTrait for AlignmentMatrix {
     type Error = ();
 
     fn new(width: usize, height: usize) -> Result<Self, Self::Error> {
-        let buffer = Vec::with_capacity(width * height);
+        let buffer = vec![StepMask::STOP; width * height];
         Ok(Self {
             width,
             height,
@@ -30,6 +30,14 @@ impl AlignmentMatrixTrait for AlignmentMatrix {
         })
     }
 
+    fn resize(&mut self, width: usize, height: usize) -> Result<(), Self::Error> {
+        self.buffer.clear();
+        self.buffer.resize(width * height, StepMask::STOP);
+        self.width = width;
+        self.height = height;
+        Ok(())
+    }
+
     fn width(&self) -> usize {
         self.width
     }
@@ -62,3 +70,36 @@ impl fmt::Debug for AlignmentMatrix {
         writeln!(form)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pair::alignment_set::AlignmentSet;
+    use crate::pair::needleman_wunsch::NeedlemanWunsch;
+
+    #[test]
+    fn matrices_can_be_reused() {
+        let pairs: [(&[u8], &[u8]); 3] = [
+            (b"ACGTACGT", b"ACGACGT"),
+            (b"AC", b"ACGT"),
+            (b"ACGTACGTACGT", b"ACGT"),
+        ];
+        let strategy = NeedlemanWunsch::new(1, -1, -1, -1);
+        let mut matrix = AlignmentMatrix::new(0, 0).unwrap();
+        for (x, y) in pairs.iter() {
+            let reused = AlignmentSet::builder(x.len(), y.len(), strategy.clone())
+                .with_matrix(matrix)
+                .build(|i, j| x[i] == y[j])
+                .unwrap();
+            let fresh: AlignmentSet<AlignmentMatrix> =
+                AlignmentSet::new(x.len(), y.len(), strategy.clone(), |i, j| x[i] == y[j]).unwrap();
+            assert_eq!(reused.global_score(), fresh.global_score());
+            let steps: Vec<_> = reused.global_alignment().steps().collect();
+            let expected: Vec<_> = fresh.global_alignment().steps().collect();
+            assert_eq!(steps, expected);
+            matrix = reused.into_matrix();
+        }
+        assert_eq!(matrix.width(), 13);
+        assert!(matrix.buffer.capacity() >= 13 * 5);
+    }
+}
@@ -1,8 +1,7 @@
-use std::{fmt, fs, io, mem};
+use std::{fmt, io, mem};
 
 use memmap2::MmapMut;
-use tempfile::tempdir;
-use uuid::Uuid;
+use tempfile::tempfile;
 
 use crate::pair::cursor::Cursor;
 use crate::pair::step_mask::StepMask;
@@ -25,17 +24,13 @@ impl AlignmentMatrixTrait for AlignmentMatrix {
     type Error = io::Error;
 
     fn new(width: usize, height: usize) -> Result<Self, Self::Error> {
-        let tempdir = tempdir()?;
-        let directory = tempdir.path().join("seal");
-        let uuid = Uuid::new_v4();
-        let filename = uuid.to_simple().to_string();
-        let path = directory.join(filename);
-        let file = fs::OpenOptions::new()
-            .read(true)
-            .write(true)
-            .create(true)
-            .open(&path)?;
-        let size = (width + 1) * (height + 1);
+        // Empty files cannot be mapped.
+        let size = width * height;
+        if size == 0 {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "empty matrix"));
+        }
+        // The file gets removed by the OS once it's closed and unmapped.
+        let file = tempfile()?;
         file.set_len(size as u64)?;
         let mmap = unsafe { MmapMut::map_mut(&file)? };
         Ok(Self {
@@ -81,3 +76,35 @@ impl fmt::Debug for AlignmentMatrix {
         writeln!(form)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_matrices_are_rejected() {
+        for (width, height) in [(0, 0), (0, 3), (3, 0)] {
+            let error = AlignmentMatrix::new(width, height).err().unwrap();
+            assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+        }
+    }
+
+    #[test]
+    fn cells_keep_every_step() {
+        // One byte per cell holds transpositions and long gaps too.
+        let masks = [
+            StepMask::STOP,
+            StepMask::ALIGN | StepMask::TRANSPOSE,
+            StepMask::DELETE | StepMask::LONG_DELETE,
+            StepMask::INSERT | StepMask::LONG_INSERT,
+        ];
+        let mut matrix = AlignmentMatrix::new(1, masks.len()).unwrap();
+        assert_eq!(matrix.mmap.len(), masks.len());
+        for (y, &mask) in masks.iter().enumerate() {
+            matrix.set_at(&Cursor { x: 0, y }, mask);
+        }
+        for (y, &mask) in masks.iter().enumerate() {
+            assert_eq!(matrix.at(&Cursor { x: 0, y }), mask);
+        }
+    }
+}
@@ -1,13 +1,33 @@
 use crate::pair::{cursor::Cursor, step_mask::StepMask};
 
+pub mod anti_diagonal;
+pub mod banded;
+pub mod buffered;
 pub mod in_memory;
+#[cfg(feature = "memory-mapped")]
 pub mod memory_mapped;
+pub mod packed;
+pub mod run_length;
+pub mod score_only;
+pub mod sparse;
 
 pub trait AlignmentMatrix: Sized {
     type Error;
 
     fn new(width: usize, height: usize) -> Result<Self, Self::Error>;
 
+    // Only cells with `|x - y| <= bandwidth` will get accessed.
+    fn with_bandwidth(width: usize, height: usize, _bandwidth: usize) -> Result<Self, Self::Error> {
+        Self::new(width, height)
+    }
+
+    // Resizes the matrix to `width` by `height` cells, all `STOP`, reusing
+    // its allocation where possible.
+    fn resize(&mut self, width: usize, height: usize) -> Result<(), Self::Error> {
+        *self = Self::new(width, height)?;
+        Ok(())
+    }
+
     fn width(&self) -> usize;
     fn height(&self) -> usize;
 
@@ -0,0 +1,135 @@
+use std::fmt;
+
+use crate::pair::cursor::Cursor;
+use crate::pair::step_mask::StepMask;
+
+use super::AlignmentMatrix as AlignmentMatrixTrait;
+
+// Bits of `StepMask` stored per cell: ALIGN, DELETE and INSERT.
+const BITS: usize = 3;
+const MASK: u16 = 0b111;
+
+// Packs the step masks of cells into three bits each, for strategies whose
+// paths only ever align, delete or insert (i.e. no transpositions). Writing
+// any other step panics rather than losing it.
+pub struct AlignmentMatrix {
+    width: usize,
+    height: usize,
+    buffer: Vec<u8>,
+}
+
+impl AlignmentMatrix {
+    fn offset(&self, cursor: &Cursor) -> usize {
+        (cursor.x + (cursor.y * self.width)) * BITS
+    }
+
+    // Cells may straddle two bytes, so they get read as a `u16`.
+    fn word(&self, byte: usize) -> u16 {
+        u16::from_le_bytes([self.buffer[byte], self.buffer[byte + 1]])
+    }
+}
+
+impl AlignmentMatrixTrait for AlignmentMatrix {
+    type Error = ();
+
+    fn new(width: usize, height: usize) -> Result<Self, Self::Error> {
+        // One byte of padding for the `u16` reads of the last cell.
+        let buffer = vec![0; (width * height * BITS).div_ceil(8) + 1];
+        Ok(Self {
+            width,
+            height,
+            buffer,
+        })
+    }
+
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn at(&self, cursor: &Cursor) -> StepMask {
+        let offset = self.offset(cursor);
+        let bits = (self.word(offset / 8) >> (offset % 8)) & MASK;
+        StepMask::from_bits_truncate((bits as u8) << 1)
+    }
+
+    fn set_at(&mut self, cursor: &Cursor, step_mask: StepMask) {
+        assert!(
+            (StepMask::ALIGN | StepMask::DELETE | StepMask::INSERT).contains(step_mask),
+            "Invalid step_mask."
+        );
+        let offset = self.offset(cursor);
+        let (byte, shift) = (offset / 8, offset % 8);
+        let bits = ((step_mask.bits() >> 1) as u16) & MASK;
+        let word = (self.word(byte) & !(MASK << shift)) | (bits << shift);
+        self.buffer[byte..byte + 2].copy_from_slice(&word.to_le_bytes());
+    }
+}
+
+impl fmt::Debug for AlignmentMatrix {
+    fn fmt(&self, form: &mut fmt::Formatter) -> fmt::Result {
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                let cursor = Cursor { x, y };
+                let _ = write!(form, "{:?}\t", self.at(&cursor));
+            }
+            let _ = writeln!(form);
+        }
+        writeln!(form)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cells_straddling_bytes_keep_their_neighbours() {
+        // Cell 2 takes bits 6 to 8, the last one bits 21 to 23.
+        let mut matrix = AlignmentMatrix::new(4, 2).unwrap();
+        assert_eq!(matrix.buffer.len(), 4);
+        let masks = [
+            StepMask::ALIGN | StepMask::DELETE | StepMask::INSERT,
+            StepMask::DELETE,
+            StepMask::ALIGN | StepMask::INSERT,
+            StepMask::INSERT,
+            StepMask::STOP,
+            StepMask::ALIGN,
+            StepMask::DELETE | StepMask::INSERT,
+            StepMask::ALIGN | StepMask::DELETE | StepMask::INSERT,
+        ];
+        let cursor = |i: usize| Cursor { x: i % 4, y: i / 4 };
+        for (i, &mask) in masks.iter().enumerate() {
+            matrix.set_at(&cursor(i), mask);
+        }
+        for (i, &mask) in masks.iter().enumerate() {
+            assert_eq!(matrix.at(&cursor(i)), mask);
+        }
+        // Overwriting a straddling cell clears its old bits only.
+        matrix.set_at(&cursor(2), StepMask::STOP);
+        assert_eq!(matrix.at(&cursor(1)), masks[1]);
+        assert_eq!(matrix.at(&cursor(2)), StepMask::STOP);
+        assert_eq!(matrix.at(&cursor(3)), masks[3]);
+        assert_eq!(matrix.buffer[3], 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid step_mask.")]
+    fn transpositions_are_rejected() {
+        let mut matrix = AlignmentMatrix::new(2, 2).unwrap();
+        matrix.set_at(&Cursor { x: 1, y: 1 }, StepMask::TRANSPOSE);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid step_mask.")]
+    fn long_gaps_are_rejected() {
+        let mut matrix = AlignmentMatrix::new(2, 2).unwrap();
+        matrix.set_at(
+            &Cursor { x: 1, y: 0 },
+            StepMask::DELETE | StepMask::LONG_DELETE,
+        );
+    }
+}
@@ -0,0 +1,237 @@
+use std::fmt;
+
+use crate::pair::cursor::Cursor;
+use crate::pair::step_mask::StepMask;
+
+use super::AlignmentMatrix as AlignmentMatrixTrait;
+
+// Stores each row as runs of equal step masks, which stay few for similar
+// sequences, where most cells of a row are gaps away from the diagonal.
+// Writes in the row-major order of fills extend the last run of a row or
+// append one; other writes split runs.
+pub struct AlignmentMatrix {
+    width: usize,
+    height: usize,
+    rows: Vec<Row>,
+}
+
+// The first x and step mask of every run by increasing x, taking 8 bytes
+// per run. Cells from `len` on have not been written and are `STOP`.
+#[derive(Clone, Default)]
+struct Row {
+    runs: Vec<(u32, StepMask)>,
+    len: u32,
+}
+
+impl Row {
+    fn run(&self, x: u32) -> usize {
+        self.runs.partition_point(|&(start, _)| start <= x) - 1
+    }
+
+    fn at(&self, x: u32) -> StepMask {
+        if x < self.len {
+            self.runs[self.run(x)].1
+        } else {
+            StepMask::STOP
+        }
+    }
+
+    fn push(&mut self, x: u32, step_mask: StepMask) {
+        if step_mask == StepMask::STOP {
+            return;
+        }
+        // Skipped cells stay `STOP`.
+        let last = |runs: &[(u32, StepMask)]| runs.last().map(|&(_, mask)| mask);
+        if x > self.len && last(&self.runs) != Some(StepMask::STOP) {
+            self.runs.push((self.len, StepMask::STOP));
+        }
+        if last(&self.runs) != Some(step_mask) {
+            self.runs.push((x, step_mask));
+        }
+        self.len = x + 1;
+    }
+
+    // Splits the run around the cell, then merges it with its neighbours.
+    fn set(&mut self, x: u32, step_mask: StepMask) {
+        let index = self.run(x);
+        let (start, old) = self.runs[index];
+        if old == step_mask {
+            return;
+        }
+        let end = self
+            .runs
+            .get(index + 1)
+            .map_or(self.len, |&(start, _)| start);
+        if x + 1 < end {
+            self.runs.insert(index + 1, (x + 1, old));
+        }
+        let index = if start < x {
+            self.runs.insert(index + 1, (x, step_mask));
+            index + 1
+        } else {
+            self.runs[index].1 = step_mask;
+            index
+        };
+        if index + 1 < self.runs.len() && self.runs[index + 1].1 == step_mask {
+            self.runs.remove(index + 1);
+        }
+        if index > 0 && self.runs[index - 1].1 == step_mask {
+            self.runs.remove(index);
+        }
+    }
+}
+
+impl AlignmentMatrix {
+    // The number of runs stored.
+    pub fn runs(&self) -> usize {
+        self.rows.iter().map(|row| row.runs.len()).sum()
+    }
+}
+
+impl AlignmentMatrixTrait for AlignmentMatrix {
+    // The width does not fit the `u32` starts of runs.
+    type Error = ();
+
+    fn new(width: usize, height: usize) -> Result<Self, Self::Error> {
+        if u32::try_from(width).is_err() {
+            return Err(());
+        }
+        Ok(Self {
+            width,
+            height,
+            rows: vec![Row::default(); height],
+        })
+    }
+
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn at(&self, cursor: &Cursor) -> StepMask {
+        self.rows[cursor.y].at(cursor.x as u32)
+    }
+
+    fn set_at(&mut self, cursor: &Cursor, step_mask: StepMask) {
+        let row = &mut self.rows[cursor.y];
+        let x = cursor.x as u32;
+        if x >= row.len {
+            row.push(x, step_mask);
+        } else {
+            row.set(x, step_mask);
+        }
+    }
+}
+
+impl fmt::Debug for AlignmentMatrix {
+    fn fmt(&self, form: &mut fmt::Formatter) -> fmt::Result {
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                let cursor = Cursor { x, y };
+                let _ = write!(form, "{:?}\t", self.at(&cursor));
+            }
+            let _ = writeln!(form);
+        }
+        writeln!(form)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pair::alignment_set::AlignmentSet;
+    use crate::pair::needleman_wunsch::NeedlemanWunsch;
+
+    #[test]
+    fn wide_rows_are_rejected() {
+        let width = u32::MAX as usize + 1;
+        assert!(AlignmentMatrix::new(width, 0).is_err());
+        assert!(AlignmentMatrix::new(width - 1, 0).is_ok());
+    }
+
+    #[test]
+    fn rewriting_a_cell_merges_its_run_back() {
+        let mut matrix = AlignmentMatrix::new(5, 1).unwrap();
+        matrix.set_at(&Cursor { x: 4, y: 0 }, StepMask::DELETE);
+        matrix.set_at(&Cursor { x: 2, y: 0 }, StepMask::ALIGN);
+        assert_eq!(matrix.runs(), 4);
+        matrix.set_at(&Cursor { x: 2, y: 0 }, StepMask::STOP);
+        assert_eq!(
+            matrix.rows[0].runs,
+            vec![(0, StepMask::STOP), (4, StepMask::DELETE)]
+        );
+    }
+
+    #[test]
+    fn identical_sequences_take_few_runs() {
+        // Rows are mostly insertions, then deletions.
+        let x = b"The quick brown fox jumps over the lazy dog.";
+        let strategy = NeedlemanWunsch::new(1, -1, -1, -1);
+        let set: AlignmentSet<AlignmentMatrix> =
+            AlignmentSet::new(x.len(), x.len(), strategy, |i, j| x[i] == x[j]).unwrap();
+        assert!(set.matrix().runs() * 4 < (x.len() + 1) * (x.len() + 1));
+    }
+
+    #[test]
+    fn row_major_writes_append_runs() {
+        let masks = [
+            StepMask::DELETE,
+            StepMask::DELETE,
+            StepMask::ALIGN,
+            StepMask::ALIGN | StepMask::INSERT,
+            StepMask::ALIGN | StepMask::INSERT,
+            StepMask::STOP,
+        ];
+        let mut matrix = AlignmentMatrix::new(masks.len(), 1).unwrap();
+        for (x, &mask) in masks.iter().enumerate() {
+            matrix.set_at(&Cursor { x, y: 0 }, mask);
+        }
+        assert_eq!(
+            matrix.rows[0].runs,
+            vec![
+                (0, StepMask::DELETE),
+                (2, StepMask::ALIGN),
+                (3, StepMask::ALIGN | StepMask::INSERT),
+            ]
+        );
+        for (x, &mask) in masks.iter().enumerate() {
+            assert_eq!(matrix.at(&Cursor { x, y: 0 }), mask);
+        }
+        assert_eq!(std::mem::size_of::<(u32, StepMask)>(), 8);
+    }
+
+    #[test]
+    fn writes_out_of_order_split_and_merge_runs() {
+        let mut matrix = AlignmentMatrix::new(6, 1).unwrap();
+        let cursor = |x| Cursor { x, y: 0 };
+        // Skipping cells leaves them `STOP`.
+        matrix.set_at(&cursor(3), StepMask::DELETE);
+        assert_eq!(matrix.at(&cursor(1)), StepMask::STOP);
+        assert_eq!(matrix.at(&cursor(5)), StepMask::STOP);
+        matrix.set_at(&cursor(4), StepMask::DELETE);
+        matrix.set_at(&cursor(1), StepMask::DELETE);
+        assert_eq!(matrix.runs(), 4);
+        matrix.set_at(&cursor(2), StepMask::DELETE);
+        assert_eq!(
+            matrix.rows[0].runs,
+            vec![(0, StepMask::STOP), (1, StepMask::DELETE)]
+        );
+        matrix.set_at(&cursor(3), StepMask::ALIGN);
+        let row: Vec<_> = (0..6).map(|x| matrix.at(&cursor(x))).collect();
+        assert_eq!(
+            row,
+            vec![
+                StepMask::STOP,
+                StepMask::DELETE,
+                StepMask::DELETE,
+                StepMask::ALIGN,
+                StepMask::DELETE,
+                StepMask::STOP,
+            ]
+        );
+        assert_eq!(matrix.runs(), 4);
+    }
+}
@@ -0,0 +1,57 @@
+use crate::pair::cursor::Cursor;
+use crate::pair::step_mask::StepMask;
+
+use super::AlignmentMatrix as AlignmentMatrixTrait;
+
+// Stores no step masks at all, for when only the scores are needed. The
+// fill itself keeps a single row of scores, so this takes O(width) memory.
+// Tracebacks stop right away, yielding alignments without steps.
+#[derive(Debug)]
+pub struct AlignmentMatrix {
+    width: usize,
+    height: usize,
+}
+
+impl AlignmentMatrixTrait for AlignmentMatrix {
+    type Error = ();
+
+    fn new(width: usize, height: usize) -> Result<Self, Self::Error> {
+        Ok(Self { width, height })
+    }
+
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn at(&self, _cursor: &Cursor) -> StepMask {
+        StepMask::STOP
+    }
+
+    fn set_at(&mut self, _cursor: &Cursor, _step_mask: StepMask) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pair::alignment_matrix::in_memory::AlignmentMatrix as InMemoryAlignmentMatrix;
+    use crate::pair::alignment_set::AlignmentSet;
+    use crate::pair::needleman_wunsch::NeedlemanWunsch;
+
+    #[test]
+    fn scores_are_kept() {
+        let x = b"GATTACA";
+        let y = b"GCATGCU";
+        let strategy = NeedlemanWunsch::new(1, -1, -1, -1);
+        let set: AlignmentSet<AlignmentMatrix> =
+            AlignmentSet::new(x.len(), y.len(), strategy.clone(), |i, j| x[i] == y[j]).unwrap();
+        let dense: AlignmentSet<InMemoryAlignmentMatrix> =
+            AlignmentSet::new(x.len(), y.len(), strategy, |i, j| x[i] == y[j]).unwrap();
+        assert_eq!(set.global_score(), dense.global_score());
+        assert_eq!(set.local_score(), dense.local_score());
+        assert_eq!(set.global_alignment().steps().count(), 0);
+    }
+}
@@ -0,0 +1,85 @@
+use std::collections::HashMap;
+
+use crate::pair::cursor::Cursor;
+use crate::pair::step_mask::StepMask;
+
+use super::AlignmentMatrix as AlignmentMatrixTrait;
+
+// Stores only cells with a step mask other than `STOP`, for strategies that
+// touch a tiny fraction of the matrix, like seed chaining or A* searches.
+#[derive(Debug)]
+pub struct AlignmentMatrix {
+    width: usize,
+    height: usize,
+    cells: HashMap<(usize, usize), StepMask>,
+}
+
+impl AlignmentMatrix {
+    // The number of cells stored.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+}
+
+impl AlignmentMatrixTrait for AlignmentMatrix {
+    type Error = ();
+
+    fn new(width: usize, height: usize) -> Result<Self, Self::Error> {
+        Ok(Self {
+            width,
+            height,
+            cells: HashMap::new(),
+        })
+    }
+
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn at(&self, cursor: &Cursor) -> StepMask {
+        let cell = (cursor.x, cursor.y);
+        self.cells.get(&cell).cloned().unwrap_or(StepMask::STOP)
+    }
+
+    fn set_at(&mut self, cursor: &Cursor, step_mask: StepMask) {
+        let cell = (cursor.x, cursor.y);
+        if step_mask == StepMask::STOP {
+            self.cells.remove(&cell);
+        } else {
+            self.cells.insert(cell, step_mask);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pair::alignment_set::AlignmentSet;
+    use crate::pair::needleman_wunsch::NeedlemanWunsch;
+
+    #[test]
+    fn only_touched_cells_are_stored() {
+        let mut matrix = AlignmentMatrix::new(1000, 1000).unwrap();
+        matrix.set_at(&Cursor { x: 10, y: 20 }, StepMask::ALIGN);
+        matrix.set_at(&Cursor { x: 11, y: 20 }, StepMask::DELETE);
+        matrix.set_at(&Cursor { x: 11, y: 20 }, StepMask::STOP);
+        assert_eq!(matrix.len(), 1);
+        assert_eq!(matrix.at(&Cursor { x: 10, y: 20 }), StepMask::ALIGN);
+        assert_eq!(matrix.at(&Cursor { x: 999, y: 999 }), StepMask::STOP);
+
+        let x = b"ACGTACGTACGT";
+        let strategy = NeedlemanWunsch::new(1, -1, -1, -1).with_bandwidth(0);
+        let set: AlignmentSet<AlignmentMatrix> =
+            AlignmentSet::new(x.len(), x.len(), strategy, |i, j| x[i] == x[j]).unwrap();
+        assert_eq!(set.global_score(), 12);
+        assert_eq!(set.matrix().len(), 12);
+    }
+}
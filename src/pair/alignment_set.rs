@@ -1,11 +1,20 @@
 use std::cmp;
+use std::io;
 
 use crate::pair::alignment::Alignment;
 use crate::pair::alignment_matrix::AlignmentMatrix;
-use crate::pair::alignments::Alignments;
+use crate::pair::alignments::{Alignments, Preference};
+use crate::pair::boundary::Boundary;
+use crate::pair::circular;
 use crate::pair::cursor::Cursor;
+use crate::pair::free_ends::FreeEnds;
 use crate::pair::step_mask::StepMask;
 use crate::pair::strategy::Strategy;
+use crate::pair::suboptimal::StepScores;
+use crate::pair::waterman_eggert::WatermanEggert;
+
+// Low enough to never win a comparison, high enough to not overflow when offset by scores.
+pub(crate) const MIN_SCORE: isize = isize::MIN / 4;
 
 #[derive(Copy, Clone)]
 struct Highscore {
@@ -13,6 +22,14 @@ struct Highscore {
     cursor: Cursor,
 }
 
+impl Highscore {
+    fn update(&mut self, highscore: Highscore) {
+        if highscore.score > self.score {
+            *self = highscore;
+        }
+    }
+}
+
 struct Highscores {
     local: Highscore,
     global: Highscore,
@@ -23,13 +40,208 @@ impl Highscores {
         if highscore.score >= self.local.score {
             self.local = highscore
         }
-        self.global = highscore;
+    }
+}
+
+// All cells sharing the best score seen so far.
+struct Optima {
+    score: isize,
+    cursors: Vec<Cursor>,
+}
+
+impl Optima {
+    fn update(&mut self, highscore: &Highscore) {
+        if highscore.score > self.score {
+            self.score = highscore.score;
+            self.cursors.clear();
+        }
+        if highscore.score == self.score && !self.cursors.contains(&highscore.cursor) {
+            self.cursors.push(highscore.cursor);
+        }
+    }
+
+    // The cells with `first` first.
+    fn into_cursors(mut self, first: &Cursor) -> Vec<Cursor> {
+        self.cursors.retain(|cursor| cursor != first);
+        self.cursors.insert(0, *first);
+        self.cursors
+    }
+}
+
+// Errors of filling an `AlignmentSet`. Unless checked, scores saturate
+// instead of overflowing.
+#[derive(Debug)]
+pub enum ScoreError<E> {
+    // A score at the cell overflowed `isize`.
+    Overflow { cursor: Cursor },
+    Matrix(E),
+}
+
+// How a fill computes its scores.
+#[derive(Copy, Clone, Default)]
+struct Fill {
+    // Fail instead of saturating when a score overflows.
+    checked: bool,
+    // Keep the score of every cell.
+    scores: bool,
+    // Some cells are blocked, see `AlignmentSetBuilder::with_blocked`.
+    blocking: bool,
+    // Keep the score of every step into every cell.
+    steps: bool,
+}
+
+pub(crate) fn calculate_cell<S: Strategy>(
+    strategy: &S,
+    previous_scores: (isize, isize, isize),
+    substitution: isize,
+    gaps: (isize, isize),
+    checked: bool,
+) -> Option<(StepMask, isize)> {
+    let (align, delete, insert) = previous_scores;
+    let align = add(align, substitution, checked)?;
+    let delete = add(delete, gaps.0, checked)?;
+    let insert = add(insert, gaps.1, checked)?;
+    let steps = strategy.step_mask(align, insert, delete);
+    let score = strategy.total_score(cmp::max(cmp::max(align, delete), insert));
+    Some((steps, score))
+}
+
+// Adds unless the sum overflows if `checked`, saturating otherwise.
+pub(crate) fn add(score: isize, offset: isize, checked: bool) -> Option<isize> {
+    if checked {
+        score.checked_add(offset)
+    } else {
+        Some(score.saturating_add(offset))
+    }
+}
+
+// Cells of the first row and column have a single (gap) predecessor.
+pub(crate) fn boundary_cell<S: Strategy>(
+    strategy: &S,
+    previous_score: isize,
+    step: StepMask,
+    gap: isize,
+    boundary: Boundary,
+    checked: bool,
+) -> Option<(StepMask, isize)> {
+    match boundary {
+        Boundary::Gaps => {}
+        Boundary::Zero => return Some((StepMask::STOP, 0)),
+        Boundary::Blocked => return Some((StepMask::STOP, MIN_SCORE)),
+    }
+    let score = add(previous_score, gap, checked)?;
+    let steps = if step == StepMask::INSERT {
+        strategy.step_mask(MIN_SCORE, score, MIN_SCORE)
+    } else {
+        strategy.step_mask(MIN_SCORE, MIN_SCORE, score)
+    };
+    Some((steps, strategy.total_score(score)))
+}
+
+// Fills an `AlignmentSet`, configured by the `with_*` methods and consumed
+// by `build` or `build_with_scores`.
+pub struct AlignmentSetBuilder<T, S, B = fn(&Cursor) -> bool> {
+    x_len: usize,
+    y_len: usize,
+    strategy: S,
+    matrix: Option<T>,
+    blocked: B,
+    fill: Fill,
+}
+
+impl<T, S, B, E> AlignmentSetBuilder<T, S, B>
+where
+    T: AlignmentMatrix<Error = E>,
+    S: Strategy,
+    B: Fn(&Cursor) -> bool,
+{
+    // Fills `matrix` instead of allocating a new one, e.g. to reuse the
+    // allocation of a previous alignment via `into_matrix`. The matrix gets
+    // resized to fit, dropping the band of banded matrices.
+    pub fn with_matrix(mut self, matrix: T) -> AlignmentSetBuilder<T, S, B> {
+        self.matrix = Some(matrix);
+        self
+    }
+
+    // Keeps the score of every cell for `score_at`, taking
+    // `size_of::<isize>()` additional bytes per cell.
+    pub fn with_scores_kept(mut self) -> AlignmentSetBuilder<T, S, B> {
+        self.fill.scores = true;
+        self
+    }
+
+    // Like `with_scores_kept`, also keeping the score of every step into
+    // every cell for `k_best`, taking `4 * size_of::<isize>()` additional
+    // bytes per cell in total.
+    pub fn with_step_scores_kept(mut self) -> AlignmentSetBuilder<T, S, B> {
+        self.fill.scores = true;
+        self.fill.steps = true;
+        self
+    }
+
+    // Fails instead of saturating when a score overflows.
+    pub fn with_overflow_checks(mut self) -> AlignmentSetBuilder<T, S, B> {
+        self.fill.checked = true;
+        self
+    }
+
+    // Cells for which `blocked` holds get no predecessors, so no alignment
+    // passes through them.
+    pub(crate) fn with_blocked<C>(self, blocked: C) -> AlignmentSetBuilder<T, S, C>
+    where
+        C: Fn(&Cursor) -> bool,
+    {
+        AlignmentSetBuilder {
+            x_len: self.x_len,
+            y_len: self.y_len,
+            strategy: self.strategy,
+            matrix: self.matrix,
+            blocked,
+            fill: Fill {
+                blocking: true,
+                ..self.fill
+            },
+        }
+    }
+
+    // x[i] and y[j] score the match score of the strategy if `f(i, j)`
+    // holds, its mismatch score otherwise.
+    pub fn build<F>(self, f: F) -> Result<AlignmentSet<T>, ScoreError<E>>
+    where
+        F: Fn(usize, usize) -> bool,
+    {
+        let (equal, align) = (self.strategy.match_score(), self.strategy.mismatch_score());
+        self.build_with_scores(|x, y| if f(x, y) { equal } else { align })
+    }
+
+    // The score of aligning x[i] with y[j] is `score(i, j)`, e.g. from a
+    // substitution matrix.
+    pub fn build_with_scores<F>(self, score: F) -> Result<AlignmentSet<T>, ScoreError<E>>
+    where
+        F: Fn(usize, usize) -> isize,
+    {
+        AlignmentSet::fill(
+            self.matrix,
+            self.x_len,
+            self.y_len,
+            &self.strategy,
+            score,
+            self.blocked,
+            self.fill,
+        )
     }
 }
 
 pub struct AlignmentSet<T> {
     matrix: T,
     highscores: Highscores,
+    rotation: usize,
+    scores: Option<Vec<isize>>,
+    step_scores: Option<StepScores>,
+    // The cells in which co-optimal local and global alignments end.
+    local_optima: Vec<Cursor>,
+    global_optima: Vec<Cursor>,
+    preference: Preference,
 }
 
 impl<T, E> AlignmentSet<T>
@@ -41,71 +253,341 @@ where
         y_len: usize,
         strategy: S,
         f: F,
-    ) -> Result<AlignmentSet<T>, E>
+    ) -> Result<AlignmentSet<T>, ScoreError<E>>
+    where
+        F: Fn(usize, usize) -> bool,
+    {
+        Self::builder(x_len, y_len, strategy).build(f)
+    }
+
+    // Like `new`, but takes the options of `AlignmentSetBuilder` before filling.
+    pub fn builder<S: Strategy>(
+        x_len: usize,
+        y_len: usize,
+        strategy: S,
+    ) -> AlignmentSetBuilder<T, S> {
+        AlignmentSetBuilder {
+            x_len,
+            y_len,
+            strategy,
+            matrix: None,
+            blocked: |_| false,
+            fill: Fill::default(),
+        }
+    }
+
+    // All local alignments scoring above `threshold` whose paths do not
+    // share any cell, by decreasing score. See `WatermanEggert`.
+    pub fn local_matches<S: Strategy, F>(
+        x_len: usize,
+        y_len: usize,
+        strategy: S,
+        threshold: isize,
+        f: F,
+    ) -> Result<Vec<Alignment>, ScoreError<E>>
     where
         F: Fn(usize, usize) -> bool,
     {
-        let width = x_len + 1;
-        let height = y_len + 1;
+        WatermanEggert::<T, _, _>::new(x_len, y_len, strategy, threshold.saturating_add(1), f)
+            .collect()
+    }
+
+    // For circular strategies the rotation of x with the best global score
+    // is kept. It is searched for in O(x_len * y_len * log(x_len)) time,
+    // unless scores depend on where the rotation starts or cells are
+    // blocked, in which case every rotation gets filled in
+    // O(x_len^2 * y_len) time.
+    fn fill<S: Strategy, F, B>(
+        matrix: Option<T>,
+        x_len: usize,
+        y_len: usize,
+        strategy: &S,
+        substitution: F,
+        blocked: B,
+        fill: Fill,
+    ) -> Result<AlignmentSet<T>, ScoreError<E>>
+    where
+        F: Fn(usize, usize) -> isize,
+        B: Fn(&Cursor) -> bool,
+    {
+        let (width, height) = (x_len + 1, y_len + 1);
+        let bandwidth = Self::bandwidth(strategy, x_len, y_len);
+        let fill_rotation = |matrix: Option<T>, rotation| {
+            let matrix = match (matrix, strategy.bandwidth()) {
+                (Some(mut matrix), _) => matrix.resize(width, height).map(|_| matrix),
+                (None, Some(_)) => T::with_bandwidth(width, height, bandwidth),
+                (None, None) => T::new(width, height),
+            };
+            let matrix = matrix.map_err(ScoreError::Matrix)?;
+            Self::fill_rotation(matrix, strategy, &substitution, &blocked, rotation, fill)
+        };
+        if !strategy.circular() || x_len == 0 {
+            return fill_rotation(matrix, 0);
+        }
+        if !fill.blocking && circular::applies(strategy, x_len, y_len) {
+            let rotation =
+                circular::best_rotation(strategy, x_len, y_len, &substitution, fill.checked)?;
+            return fill_rotation(matrix, rotation);
+        }
+        // Only the matrices of the best rotation so far and of the current
+        // one are alive at any time, the latter being reused by the next.
+        let mut best = fill_rotation(matrix, 0)?;
+        let mut spare = None;
+        for rotation in 1..x_len {
+            let set = fill_rotation(spare.take(), rotation)?;
+            if set.global_score() > best.global_score() {
+                spare = Some(std::mem::replace(&mut best, set).matrix);
+            } else {
+                spare = Some(set.matrix);
+            }
+        }
+        Ok(best)
+    }
+
+    fn fill_rotation<S: Strategy, F, B>(
+        mut matrix: T,
+        strategy: &S,
+        substitution: &F,
+        blocked: &B,
+        rotation: usize,
+        fill: Fill,
+    ) -> Result<AlignmentSet<T>, ScoreError<E>>
+    where
+        F: Fn(usize, usize) -> isize,
+        B: Fn(&Cursor) -> bool,
+    {
+        let x_len = matrix.width() - 1;
+        let y_len = matrix.height() - 1;
+        let width = matrix.width();
+        let checked = fill.checked;
+
+        let bandwidth = Self::bandwidth(strategy, x_len, y_len);
+        // Positions in x of the rotated alignment, with x_len and 0 being
+        // the same position of a circular x.
+        let circular = strategy.circular();
+        let at = |x: usize| {
+            if circular {
+                (x + rotation) % x_len
+            } else {
+                x
+            }
+        };
 
-        let mut matrix = T::new(width, height)?;
+        let free_ends = strategy.free_ends();
 
-        Self::prepare_matrix(&mut matrix);
-        let mut row = Self::prepared_row(width, &strategy);
+        let mut row = Self::prepared_row(&mut matrix, bandwidth, strategy, rotation, checked)?;
+        let mut scores = if fill.scores {
+            let mut scores = vec![MIN_SCORE; width * (y_len + 1)];
+            let stop = cmp::min(x_len, bandwidth);
+            scores[..=stop].copy_from_slice(&row[..=stop]);
+            Some(scores)
+        } else {
+            None
+        };
+        // The scores of the steps into each cell.
+        let mut incoming = if fill.steps {
+            let mut incoming = vec![[MIN_SCORE; 3]; width * (y_len + 1)];
+            if strategy.first_row() == Boundary::Gaps {
+                let stop = cmp::min(x_len, bandwidth);
+                for (x, cell) in incoming.iter_mut().enumerate().take(stop + 1).skip(1) {
+                    cell[1] = strategy.terminal_delete_score_at(at(x - 1), 0);
+                }
+            }
+            Some(incoming)
+        } else {
+            None
+        };
         let mut highscores = Self::prepared_highscores();
+        let mut end = Highscore {
+            score: MIN_SCORE,
+            cursor: Cursor { x: 0, y: 0 },
+        };
+        // Only positive local scores are tracked, as any cell can end an
+        // empty local alignment.
+        let mut local_optima = Optima {
+            score: 1,
+            cursors: vec![],
+        };
+        let mut global_optima = Optima {
+            score: MIN_SCORE,
+            cursors: vec![],
+        };
+
+        if free_ends.contains(FreeEnds::TRAILING_Y) {
+            let cursor = Cursor { x: x_len, y: 0 };
+            let highscore = Highscore {
+                score: row[x_len],
+                cursor,
+            };
+            global_optima.update(&highscore);
+            end.update(highscore);
+        }
 
         for y in 0..y_len {
-            let mut last_diagonal = strategy.total_score(strategy.mismatch_score() * (y as isize));
-            row[0] = strategy.total_score(strategy.insert_score() * ((y + 1) as isize));
-            for x in 0..x_len {
-                let previous = (last_diagonal, row[x], row[x + 1]);
-                let equal = f(x, y);
-                let (steps, score) = Self::calculate_cell(&strategy, previous, equal);
+            let (start, stop) = Self::band(bandwidth, x_len, y + 1);
+            let first = cmp::max(start, 1);
+            let mut last_diagonal = row[first - 1];
+            row[first - 1] = if start == 0 {
+                let cursor = Cursor { x: 0, y: y + 1 };
+                let gap = strategy.terminal_insert_score_at(at(0), y);
+                let (steps, score) = boundary_cell(
+                    strategy,
+                    last_diagonal,
+                    StepMask::INSERT,
+                    gap,
+                    strategy.first_column(),
+                    checked,
+                )
+                .ok_or(ScoreError::Overflow { cursor })?;
+                matrix.set_at(&cursor, steps);
+                if let (Some(incoming), Boundary::Gaps) = (&mut incoming, strategy.first_column()) {
+                    incoming[(y + 1) * width][2] = gap;
+                }
+                score
+            } else {
+                MIN_SCORE
+            };
+            for x in (first - 1)..stop {
                 let cursor = Cursor { x: x + 1, y: y + 1 };
+                let (steps, score) = if blocked(&cursor) {
+                    (StepMask::STOP, strategy.total_score(MIN_SCORE))
+                } else {
+                    let previous = (last_diagonal, row[x], row[x + 1]);
+                    let gaps = (
+                        if y + 1 == y_len {
+                            strategy.terminal_delete_score_at(at(x), y + 1)
+                        } else {
+                            strategy.delete_score_at(at(x), y + 1)
+                        },
+                        if x + 1 == x_len {
+                            strategy.terminal_insert_score_at(at(x + 1), y)
+                        } else {
+                            strategy.insert_score_at(at(x + 1), y)
+                        },
+                    );
+                    let substitution = substitution(at(x), y);
+                    if let Some(incoming) = &mut incoming {
+                        incoming[(y + 1) * width + x + 1] = [substitution, gaps.0, gaps.1];
+                    }
+                    calculate_cell(strategy, previous, substitution, gaps, checked)
+                        .ok_or(ScoreError::Overflow { cursor })?
+                };
+                if score >= local_optima.score {
+                    local_optima.cursors.push(cursor);
+                    if score > local_optima.score {
+                        local_optima.score = score;
+                        local_optima.cursors.drain(..local_optima.cursors.len() - 1);
+                    }
+                }
                 highscores.update(Highscore { cursor, score });
                 matrix.set_at(&cursor, steps);
                 let old_diagonal = row[x + 1];
                 row[x + 1] = score;
                 last_diagonal = old_diagonal;
             }
+            if let Some(scores) = scores.as_mut() {
+                let offset = (y + 1) * width;
+                scores[offset + first - 1..=offset + stop].copy_from_slice(&row[first - 1..=stop]);
+            }
+            if free_ends.contains(FreeEnds::TRAILING_Y) {
+                let cursor = Cursor { x: x_len, y: y + 1 };
+                let highscore = Highscore {
+                    score: row[x_len],
+                    cursor,
+                };
+                global_optima.update(&highscore);
+                end.update(highscore);
+            }
         }
 
-        Ok(AlignmentSet { matrix, highscores })
-    }
+        if free_ends.contains(FreeEnds::TRAILING_X) {
+            for (x, &score) in row.iter().enumerate() {
+                let cursor = Cursor { x, y: y_len };
+                global_optima.update(&Highscore { score, cursor });
+                end.update(Highscore { score, cursor });
+            }
+        }
 
-    fn calculate_cell<S: Strategy>(
-        strategy: &S,
-        previous_scores: (isize, isize, isize),
-        equal: bool,
-    ) -> (StepMask, isize) {
-        let (mut align, mut delete, mut insert) = previous_scores;
-        align += if equal {
-            strategy.match_score()
+        if row[x_len] >= end.score {
+            end = Highscore {
+                score: row[x_len],
+                cursor: Cursor { x: x_len, y: y_len },
+            };
+            global_optima.update(&end);
+        }
+        highscores.global = end;
+        let local_optima = if local_optima.cursors.is_empty() {
+            vec![highscores.local.cursor]
         } else {
-            strategy.mismatch_score()
+            local_optima.into_cursors(&highscores.local.cursor)
         };
-        delete += strategy.delete_score();
-        insert += strategy.insert_score();
-        let steps = StepMask::from_scores(align, delete, insert);
-        let score = strategy.total_score(cmp::max(cmp::max(align, delete), insert));
-        (steps, score)
+        let global_optima = global_optima.into_cursors(&highscores.global.cursor);
+        let step_scores = incoming.map(|steps| {
+            let mut ends = vec![Cursor { x: x_len, y: y_len }];
+            if free_ends.contains(FreeEnds::TRAILING_X) {
+                ends.extend((0..x_len).map(|x| Cursor { x, y: y_len }));
+            }
+            if free_ends.contains(FreeEnds::TRAILING_Y) {
+                ends.extend((0..y_len).map(|y| Cursor { x: x_len, y }));
+            }
+            StepScores { steps, ends }
+        });
+
+        Ok(AlignmentSet {
+            matrix,
+            highscores,
+            rotation,
+            scores,
+            step_scores,
+            local_optima,
+            global_optima,
+            preference: Preference::default(),
+        })
     }
 
-    fn prepare_matrix(matrix: &mut T) {
-        for y in 1..matrix.height() {
-            let cursor = Cursor { x: 0, y };
-            matrix.set_at(&cursor, StepMask::INSERT);
-        }
-        for x in 1..matrix.width() {
+    fn prepared_row<S: Strategy>(
+        matrix: &mut T,
+        bandwidth: usize,
+        strategy: &S,
+        rotation: usize,
+        checked: bool,
+    ) -> Result<Vec<isize>, ScoreError<E>> {
+        let boundary = strategy.first_row();
+        let x_len = matrix.width() - 1;
+        let mut row = vec![MIN_SCORE; matrix.width()];
+        row[0] = 0;
+        matrix.set_at(&Cursor { x: 0, y: 0 }, StepMask::STOP);
+        for x in 1..cmp::min(matrix.width(), bandwidth.saturating_add(1)) {
             let cursor = Cursor { x, y: 0 };
-            matrix.set_at(&cursor, StepMask::DELETE);
+            let gap = strategy.terminal_delete_score_at((x - 1 + rotation) % x_len, 0);
+            let (steps, score) = boundary_cell(
+                strategy,
+                row[x - 1],
+                StepMask::DELETE,
+                gap,
+                boundary,
+                checked,
+            )
+            .ok_or(ScoreError::Overflow { cursor })?;
+            matrix.set_at(&cursor, steps);
+            row[x] = score;
         }
+        Ok(row)
     }
 
-    fn prepared_row<S: Strategy>(width: usize, strategy: &S) -> Vec<isize> {
-        (0..width)
-            .map(|i| strategy.total_score(strategy.delete_score() * (i as isize)))
-            .collect()
+    // The band is widened to the length difference so that the last cell stays reachable.
+    fn bandwidth<S: Strategy>(strategy: &S, x_len: usize, y_len: usize) -> usize {
+        match strategy.bandwidth() {
+            Some(bandwidth) => cmp::max(bandwidth, x_len.abs_diff(y_len)),
+            None => usize::MAX,
+        }
+    }
+
+    fn band(bandwidth: usize, x_len: usize, y: usize) -> (usize, usize) {
+        (
+            y.saturating_sub(bandwidth),
+            cmp::min(x_len, y.saturating_add(bandwidth)),
+        )
     }
 
     fn prepared_highscores() -> Highscores {
@@ -137,6 +619,35 @@ where
         &self.highscores.global.cursor
     }
 
+    // The origins of `local_alignment` and `global_alignment`, found by
+    // following their steps back without collecting them.
+    pub fn local_start(&self) -> Cursor {
+        self.start(self.highscores.local.cursor)
+    }
+
+    pub fn global_start(&self) -> Cursor {
+        self.start(self.highscores.global.cursor)
+    }
+
+    fn start(&self, mut cursor: Cursor) -> Cursor {
+        while let Some(step) = self.preference.first(self.matrix.at(&cursor)) {
+            cursor.apply_backwards_step(step);
+        }
+        cursor
+    }
+
+    // Co-optimal steps get followed in the order of `preference` by every
+    // traceback of the set, `Preference::GAPS_RIGHT` by default.
+    pub fn with_preference(mut self, preference: Preference) -> AlignmentSet<T> {
+        self.preference = preference;
+        self
+    }
+
+    fn alignments(&self, cursor: Cursor, score: isize) -> Alignments<'_, T> {
+        let stack = vec![(StepMask::STOP, cursor, 0)];
+        Alignments::new(&self.matrix, stack, vec![], score).preferring(self.preference)
+    }
+
     pub fn local_alignment(&self) -> Alignment {
         self.local_alignments().next().unwrap()
     }
@@ -146,16 +657,678 @@ where
     }
 
     pub fn local_alignments(&self) -> Alignments<'_, T> {
-        let stack = vec![(StepMask::STOP, self.highscores.local.cursor, 0)];
-        Alignments::new(&self.matrix, stack, vec![], self.highscores.local.score)
+        self.alignments(self.highscores.local.cursor, self.highscores.local.score)
     }
 
     pub fn global_alignments(&self) -> Alignments<'_, T> {
-        let stack = vec![(StepMask::STOP, self.highscores.global.cursor, 0)];
-        Alignments::new(&self.matrix, stack, vec![], self.highscores.global.score)
+        self.alignments(self.highscores.global.cursor, self.highscores.global.score)
+    }
+
+    // Unlike the above, which only trace back from a single optimal cell,
+    // these lazily yield every distinct optimal path, ending in any of the
+    // cells sharing the optimal score.
+    pub fn co_optimal_local_alignments(&self) -> impl Iterator<Item = Alignment> + '_ {
+        let score = self.highscores.local.score;
+        self.local_optima
+            .iter()
+            .flat_map(move |cursor| self.alignments(*cursor, score))
+    }
+
+    pub fn co_optimal_global_alignments(&self) -> impl Iterator<Item = Alignment> + '_ {
+        let score = self.highscores.global.score;
+        self.global_optima
+            .iter()
+            .flat_map(move |cursor| self.alignments(*cursor, score))
+    }
+
+    // The alignment(s) ending in the cell, rather than in an optimum, scored
+    // by the cell. Like `score_at`, only available if the scores were kept
+    // and the cell is within the matrix.
+    pub fn alignment_from(&self, cursor: &Cursor) -> Option<Alignment> {
+        self.alignments_from(cursor)?.next()
+    }
+
+    pub fn alignments_from(&self, cursor: &Cursor) -> Option<Alignments<'_, T>> {
+        let score = self.score_at(cursor)?;
+        Some(self.alignments(*cursor, score))
+    }
+
+    // The number of alignments the above yield, counted in O(width * height)
+    // time without enumerating them. Saturates at `u64::MAX`.
+    pub fn count_co_optimal_local_alignments(&self) -> u64 {
+        self.count_paths(&self.local_optima)
+    }
+
+    pub fn count_co_optimal_global_alignments(&self) -> u64 {
+        self.count_paths(&self.global_optima)
+    }
+
+    // Paths end in the first cell without steps, so the number of paths
+    // from a cell is the sum of those from its predecessors. Only the last
+    // three rows are kept, as transpositions go back two.
+    fn count_paths(&self, cursors: &[Cursor]) -> u64 {
+        let width = self.matrix.width();
+        let mut cursors = cursors.to_vec();
+        cursors.sort_by_key(|cursor| (cursor.y, cursor.x));
+        let mut cursors = cursors.into_iter().peekable();
+        let last = cursors.clone().last().map_or(0, |cursor| cursor.y);
+        let mut rows = vec![vec![0u64; width]; 3];
+        let mut total = 0u64;
+        for y in 0..=last {
+            for x in 0..width {
+                let cursor = Cursor { x, y };
+                let steps = self.matrix.at(&cursor);
+                let mut count: u64 = if steps == StepMask::STOP { 1 } else { 0 };
+                let predecessors = [
+                    (StepMask::ALIGN, 1, 1),
+                    (StepMask::DELETE, 1, 0),
+                    (StepMask::INSERT, 0, 1),
+                    (StepMask::TRANSPOSE, 2, 2),
+                ];
+                for &(step, dx, dy) in predecessors.iter() {
+                    if steps.contains(step) {
+                        let paths = rows[(y + 3 - dy) % 3][x - dx];
+                        count = count.saturating_add(paths);
+                    }
+                }
+                rows[y % 3][x] = count;
+                if cursors.next_if_eq(&cursor).is_some() {
+                    total = total.saturating_add(count);
+                }
+            }
+        }
+        total
+    }
+
+    // Alignments of a circular strategy start x at this index, i.e. position
+    // `i` of an alignment refers to `(i + rotation) % x_len`.
+    pub fn rotation(&self) -> usize {
+        self.rotation
     }
 
     pub fn matrix(&self) -> &T {
         &self.matrix
     }
+
+    pub fn into_matrix(self) -> T {
+        self.matrix
+    }
+
+    // The lengths of x and y.
+    pub fn x_len(&self) -> usize {
+        self.matrix.width() - 1
+    }
+
+    pub fn y_len(&self) -> usize {
+        self.matrix.height() - 1
+    }
+
+    // Writes the scores kept by `with_scores_kept` as one line per row of
+    // the matrix, with cells separated by `delimiter` (e.g. b',' or b'\t').
+    // Cells outside of the band are left empty.
+    pub fn write_scores<W: io::Write>(&self, writer: &mut W, delimiter: u8) -> io::Result<()> {
+        let scores = self
+            .scores
+            .as_ref()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "scores were not kept"))?;
+        let width = self.matrix.width();
+        for row in scores.chunks(width) {
+            for (x, &score) in row.iter().enumerate() {
+                if x > 0 {
+                    writer.write_all(&[delimiter])?;
+                }
+                if score != MIN_SCORE {
+                    write!(writer, "{}", score)?;
+                }
+            }
+            writeln!(writer)?;
+        }
+        Ok(())
+    }
+
+    // Like `write_scores` for the steps leading into each cell, as any of
+    // `A` (align), `D` (delete), `I` (insert) and `T` (transpose).
+    pub fn write_steps<W: io::Write>(&self, writer: &mut W, delimiter: u8) -> io::Result<()> {
+        let letters = [
+            (StepMask::ALIGN, 'A'),
+            (StepMask::DELETE, 'D'),
+            (StepMask::INSERT, 'I'),
+            (StepMask::TRANSPOSE, 'T'),
+        ];
+        for y in 0..self.matrix.height() {
+            for x in 0..self.matrix.width() {
+                if x > 0 {
+                    writer.write_all(&[delimiter])?;
+                }
+                let steps = self.matrix.at(&Cursor { x, y });
+                for (step, letter) in letters.iter() {
+                    if steps.contains(*step) {
+                        write!(writer, "{}", letter)?;
+                    }
+                }
+            }
+            writeln!(writer)?;
+        }
+        Ok(())
+    }
+
+    // The `k` best global alignments by decreasing score, co-optimal or
+    // not, each once. Alignments start and end like those of
+    // `global_alignments`, and are scored by summing their steps, as global
+    // strategies do. Only available via
+    // `AlignmentSetBuilder::with_step_scores_kept`.
+    pub fn k_best(&self, k: usize) -> Option<Vec<Alignment>> {
+        let scores = self.scores.as_ref()?;
+        let step_scores = self.step_scores.as_ref()?;
+        Some(step_scores.k_best(scores, self.matrix.width(), k))
+    }
+
+    // The score of the best alignment ending in the cell, i.e. of x[..x]
+    // with y[..y]. Only available via `AlignmentSetBuilder::with_scores_kept`.
+    pub fn score_at(&self, cursor: &Cursor) -> Option<isize> {
+        let scores = self.scores.as_ref()?;
+        if cursor.x > self.x_len() || cursor.y > self.y_len() {
+            return None;
+        }
+        Some(scores[cursor.y * self.matrix.width() + cursor.x])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pair::alignment_matrix::in_memory::AlignmentMatrix as InMemoryAlignmentMatrix;
+    use crate::pair::needleman_wunsch::NeedlemanWunsch;
+    use crate::pair::positional_gaps::PositionalGaps;
+    use crate::pair::smith_waterman::SmithWaterman;
+    use crate::pair::step::Step;
+    use crate::pair::strategy::presets;
+
+    fn alignment_set<S: Strategy>(
+        x: &str,
+        y: &str,
+        strategy: S,
+    ) -> AlignmentSet<InMemoryAlignmentMatrix> {
+        let x: Vec<char> = x.chars().collect();
+        let y: Vec<char> = y.chars().collect();
+        AlignmentSet::new(x.len(), y.len(), strategy, |i, j| x[i] == y[j]).unwrap()
+    }
+
+    #[test]
+    fn wide_band_works() {
+        let x = "The quick brown fox jumps over the lazy dog.";
+        let y = "The brown dog jumps over the very lazy snail.";
+        let strategy = NeedlemanWunsch::new(1, -1, -1, -1);
+        let full = alignment_set(x, y, strategy.clone());
+        let banded = alignment_set(x, y, strategy.with_bandwidth(y.len()));
+        assert_eq!(banded.global_score(), full.global_score());
+    }
+
+    #[test]
+    fn narrow_band_works() {
+        let strategy = NeedlemanWunsch::new(1, -1, -1, -1).with_bandwidth(0);
+        let set = alignment_set("ABCD", "BCDA", strategy);
+        let alignment = set.global_alignment();
+        assert_eq!(set.global_score(), -4);
+        assert!(alignment.steps().all(|step| step.mask() == StepMask::ALIGN));
+    }
+
+    #[test]
+    fn band_is_respected() {
+        let strategy = NeedlemanWunsch::new(1, -1, -1, -1).with_bandwidth(2);
+        let set = alignment_set("AAAAXXXXXXBBBB", "AAAABBBB", strategy);
+        let mut cursor = *set.global_alignment().origin();
+        for step in set.global_alignment().steps() {
+            cursor.apply_forwards_step(step.mask());
+            // The band gets widened to the length difference.
+            assert!(cursor.x.abs_diff(cursor.y) <= 6);
+        }
+        assert_eq!(cursor, Cursor { x: 14, y: 8 });
+    }
+
+    #[test]
+    fn local_alignment_works() {
+        let set = alignment_set("XXXABCXXX", "YYABCYY", SmithWaterman::new(2, -1, -1, -1));
+        let alignment = set.local_alignment();
+        assert_eq!(set.local_score(), 6);
+        assert_eq!(alignment.origin(), &Cursor { x: 3, y: 2 });
+        assert_eq!(alignment.len(), 3);
+    }
+
+    #[test]
+    fn semi_global_works() {
+        let set = alignment_set("XXXXACGTXXXX", "ACGT", presets::semi_global(1, -1, -1, -1));
+        let alignment = set.global_alignment();
+        assert_eq!(set.global_score(), 4);
+        assert_eq!(set.global_max(), &Cursor { x: 8, y: 4 });
+        assert_eq!(alignment.origin(), &Cursor { x: 4, y: 0 });
+        assert!(alignment.steps().all(|step| step.mask() == StepMask::ALIGN));
+    }
+
+    #[test]
+    fn semi_global_aligns_whole_query() {
+        let set = alignment_set("XXXXACGTXXXX", "AGGT", presets::semi_global(1, -1, -1, -1));
+        let alignment = set.global_alignment();
+        assert_eq!(set.global_score(), 2);
+        assert_eq!(alignment.origin().y, 0);
+        assert_eq!(set.global_max().y, 4);
+    }
+
+    #[test]
+    fn fitting_works() {
+        let set = alignment_set("ACGT", "XXXXACCTXXXX", presets::fitting(1, -1, -1, -1));
+        let alignment = set.global_alignment();
+        assert_eq!(set.global_score(), 2);
+        assert_eq!(alignment.y_range(), 4..8);
+        assert_eq!(alignment.origin().x, 0);
+        assert_eq!(alignment.end().x, 4);
+    }
+
+    #[test]
+    fn overlap_works() {
+        let set = alignment_set("XXXXACGT", "ACGTYYYY", presets::overlap(1, -1, -1, -1));
+        let alignment = set.global_alignment();
+        assert_eq!(set.global_score(), 4);
+        assert_eq!(alignment.origin(), &Cursor { x: 4, y: 0 });
+        assert_eq!(alignment.end(), Cursor { x: 8, y: 4 });
+    }
+
+    #[test]
+    fn free_ends_work() {
+        let strategy = NeedlemanWunsch::new(1, -1, -1, -1);
+        let set = alignment_set("XXACGT", "ACGTYY", strategy.clone());
+        assert_eq!(set.global_score(), 0);
+
+        let strategy = strategy.with_free_ends(FreeEnds::all());
+        let set = alignment_set("XXACGT", "ACGTYY", strategy.clone());
+        let alignment = set.global_alignment();
+        assert_eq!(set.global_score(), 4);
+        assert_eq!(alignment.origin(), &Cursor { x: 2, y: 0 });
+        assert_eq!(alignment.end(), Cursor { x: 6, y: 4 });
+
+        // Only the leading gaps in y are free, trailing ones are penalized.
+        let strategy = strategy.with_free_ends(FreeEnds::LEADING_Y);
+        let set = alignment_set("ACGT", "YYACGTY", strategy);
+        assert_eq!(set.global_score(), 3);
+        assert_eq!(set.global_alignment().origin(), &Cursor { x: 0, y: 2 });
+    }
+
+    #[test]
+    fn local_matches_work() {
+        let x: Vec<char> = "ACGTACXXXXACGTXXACG".chars().collect();
+        let y: Vec<char> = "YYACGTACYY".chars().collect();
+        let strategy = SmithWaterman::new(2, -1, -1, -1);
+        let matches: Vec<Alignment> = AlignmentSet::<InMemoryAlignmentMatrix>::local_matches(
+            x.len(),
+            y.len(),
+            strategy,
+            6,
+            |i, j| x[i] == y[j],
+        )
+        .unwrap();
+        let scores: Vec<isize> = matches.iter().map(|a| a.score()).collect();
+        assert_eq!(scores, vec![12, 10]);
+        assert_eq!(matches[1].origin(), &Cursor { x: 10, y: 2 });
+    }
+
+    #[test]
+    fn circular_works() {
+        let strategy = NeedlemanWunsch::new(1, -1, -1, -1);
+        let set = alignment_set("GTACCA", "CAGTAC", strategy.clone());
+        assert_eq!(set.global_score(), 0);
+
+        let set = alignment_set("GTACCA", "CAGTAC", strategy.clone().with_circular());
+        assert_eq!(set.global_score(), 6);
+        assert_eq!(set.rotation(), 4);
+    }
+
+    #[test]
+    fn circular_finds_the_best_rotation() {
+        // A small linear congruential generator keeps the cases reproducible.
+        let mut state = 7u64;
+        let mut next = |n: usize| {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (state >> 33) as usize % n
+        };
+        let strategies = [
+            NeedlemanWunsch::new(1, -1, -1, -1),
+            NeedlemanWunsch::new(2, -1, -2, -1),
+            NeedlemanWunsch::new(2, -1, -1, -2)
+                .with_free_ends(FreeEnds::LEADING_X | FreeEnds::TRAILING_X),
+            NeedlemanWunsch::new(1, -1, -2, -2).with_free_ends(FreeEnds::all()),
+            NeedlemanWunsch::new(1, -1, -1, -1).with_terminal_gaps(0, -2),
+        ];
+        for _ in 0..100 {
+            let x: String = (0..1 + next(12))
+                .map(|_| b"ACGT"[next(4)] as char)
+                .collect();
+            let y: String = (0..next(12)).map(|_| b"ACGT"[next(4)] as char).collect();
+            for strategy in &strategies {
+                let mut best = (MIN_SCORE, 0);
+                for rotation in 0..x.len() {
+                    let rotated = format!("{}{}", &x[rotation..], &x[..rotation]);
+                    let score = alignment_set(&rotated, &y, strategy.clone()).global_score();
+                    if score > best.0 {
+                        best = (score, rotation);
+                    }
+                }
+                let set = alignment_set(&x, &y, strategy.clone().with_circular());
+                assert_eq!((set.global_score(), set.rotation()), best, "{} {}", x, y);
+                assert_eq!(set.global_alignment().score(), best.0);
+            }
+        }
+    }
+
+    #[test]
+    fn long_circular_x_works() {
+        let mut state = 11u64;
+        let x: Vec<u8> = (0..3000)
+            .map(|_| {
+                state = state
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                b"ACGT"[(state >> 33) as usize % 4]
+            })
+            .collect();
+        // y spans the origin of x.
+        let y: Vec<u8> = x[2950..].iter().chain(&x[..50]).copied().collect();
+        let strategy = NeedlemanWunsch::new(1, -1, -1, -1)
+            .with_free_ends(FreeEnds::LEADING_X | FreeEnds::TRAILING_X)
+            .with_circular();
+        let set: AlignmentSet<InMemoryAlignmentMatrix> =
+            AlignmentSet::new(x.len(), y.len(), strategy, |i, j| x[i] == y[j]).unwrap();
+        assert_eq!(set.global_score(), 100);
+    }
+
+    #[test]
+    fn substitution_works() {
+        // Vowels are interchangeable at a small cost.
+        let vowel = |c: &char| "aeiou".contains(*c);
+        let substitution = |a: &char, b: &char| match (a == b, vowel(a) && vowel(b)) {
+            (true, _) => 2,
+            (false, true) => 1,
+            (false, false) => -2,
+        };
+        let x: Vec<char> = "color".chars().collect();
+        let y: Vec<char> = "culer".chars().collect();
+        let strategy = NeedlemanWunsch::new(0, 0, -2, -2);
+        let set: AlignmentSet<InMemoryAlignmentMatrix> =
+            AlignmentSet::builder(x.len(), y.len(), strategy)
+                .build_with_scores(|i, j| substitution(&x[i], &y[j]))
+                .unwrap();
+        assert_eq!(set.global_score(), 8);
+    }
+
+    #[test]
+    fn overflows_are_detected() {
+        let strategy = NeedlemanWunsch::new(isize::MAX / 2, -1, -1, -1);
+        let x: Vec<char> = "AAA".chars().collect();
+        let set: AlignmentSet<InMemoryAlignmentMatrix> =
+            AlignmentSet::new(x.len(), x.len(), strategy.clone(), |i, j| x[i] == x[j]).unwrap();
+        assert_eq!(set.global_score(), isize::MAX);
+
+        let result: Result<AlignmentSet<InMemoryAlignmentMatrix>, _> =
+            AlignmentSet::builder(x.len(), x.len(), strategy)
+                .with_overflow_checks()
+                .build(|i, j| x[i] == x[j]);
+        match result {
+            Err(ScoreError::Overflow { cursor }) => assert_eq!(cursor, Cursor { x: 3, y: 3 }),
+            _ => panic!("Overflow not detected."),
+        }
+    }
+
+    #[test]
+    fn terminal_gaps_work() {
+        let strategy = NeedlemanWunsch::new(2, -1, -2, -2);
+        let set = alignment_set("XACGTX", "ACGT", strategy.clone());
+        assert_eq!(set.global_score(), 8 - 4);
+
+        let set = alignment_set("XACGTX", "ACGT", strategy.with_terminal_gaps(-1, -1));
+        assert_eq!(set.global_score(), 8 - 2);
+        assert_eq!(set.global_alignment().steps().count(), 6);
+
+        // Internal gaps are not affected.
+        let strategy = NeedlemanWunsch::new(2, -1, -2, -2).with_terminal_gaps(0, 0);
+        let set = alignment_set("ACGXXTACG", "ACGTACG", strategy);
+        assert_eq!(set.global_score(), 14 - 4);
+    }
+
+    #[test]
+    fn boundaries_work() {
+        let strategy = NeedlemanWunsch::new(2, -1, -2, -2);
+        let set = alignment_set("XXACGT", "ACGT", strategy.clone());
+        assert_eq!(set.global_score(), 8 - 4);
+
+        let zero = strategy
+            .clone()
+            .with_boundaries(Boundary::Zero, Boundary::Gaps);
+        let set = alignment_set("XXACGT", "ACGT", zero);
+        assert_eq!(set.global_score(), 8);
+        assert_eq!(set.global_alignment().origin(), &Cursor { x: 2, y: 0 });
+
+        // Without leading deletions the Xs have to be aligned.
+        let blocked = strategy.with_boundaries(Boundary::Blocked, Boundary::Gaps);
+        let set = alignment_set("XXACGT", "ACGT", blocked);
+        assert_eq!(set.global_score(), 1);
+        assert_eq!(
+            set.global_alignment().steps().next().unwrap().mask(),
+            StepMask::ALIGN
+        );
+    }
+
+    #[test]
+    fn scores_are_kept() {
+        let x: Vec<char> = "ACGT".chars().collect();
+        let y: Vec<char> = "AGT".chars().collect();
+        let strategy = NeedlemanWunsch::new(1, -1, -1, -1);
+        let set: AlignmentSet<InMemoryAlignmentMatrix> =
+            AlignmentSet::builder(x.len(), y.len(), strategy.clone())
+                .with_scores_kept()
+                .build(|i, j| x[i] == y[j])
+                .unwrap();
+        assert_eq!((set.x_len(), set.y_len()), (4, 3));
+        let row: Vec<_> = (0..=4)
+            .map(|x| set.score_at(&Cursor { x, y: 1 }).unwrap())
+            .collect();
+        assert_eq!(row, vec![-1, 1, 0, -1, -2]);
+        assert_eq!(
+            set.score_at(&Cursor { x: 4, y: 3 }),
+            Some(set.global_score())
+        );
+        assert_eq!(set.score_at(&Cursor { x: 5, y: 0 }), None);
+
+        // Options combine, e.g. keeping the scores of a reused matrix.
+        let reused = AlignmentSet::builder(y.len(), x.len(), strategy.clone())
+            .with_matrix(set.into_matrix())
+            .with_scores_kept()
+            .with_overflow_checks()
+            .build(|i, j| y[i] == x[j])
+            .unwrap();
+        assert_eq!(reused.score_at(&Cursor { x: 3, y: 4 }), Some(2));
+
+        let set = alignment_set("ACGT", "AGT", strategy);
+        assert_eq!(set.score_at(&Cursor { x: 0, y: 0 }), None);
+    }
+
+    #[test]
+    fn tables_are_written() {
+        let x: Vec<char> = "AC".chars().collect();
+        let y: Vec<char> = "A".chars().collect();
+        let strategy = NeedlemanWunsch::new(1, -1, -1, -1);
+        let set: AlignmentSet<InMemoryAlignmentMatrix> =
+            AlignmentSet::builder(x.len(), y.len(), strategy.clone())
+                .with_scores_kept()
+                .build(|i, j| x[i] == y[j])
+                .unwrap();
+        let mut csv = vec![];
+        set.write_scores(&mut csv, b',').unwrap();
+        assert_eq!(String::from_utf8(csv).unwrap(), "0,-1,-2\n-1,1,0\n");
+        let mut tsv = vec![];
+        set.write_steps(&mut tsv, b'\t').unwrap();
+        assert_eq!(String::from_utf8(tsv).unwrap(), "\tD\tD\nI\tA\tD\n");
+
+        let set = alignment_set("AC", "A", strategy);
+        assert!(set.write_scores(&mut vec![], b',').is_err());
+    }
+
+    fn k_best<S: Strategy>(x: &str, y: &str, strategy: S, k: usize) -> Vec<Alignment> {
+        let x: Vec<char> = x.chars().collect();
+        let y: Vec<char> = y.chars().collect();
+        let set: AlignmentSet<InMemoryAlignmentMatrix> =
+            AlignmentSet::builder(x.len(), y.len(), strategy)
+                .with_step_scores_kept()
+                .build(|i, j| x[i] == y[j])
+                .unwrap();
+        let alignments = set.k_best(k).unwrap();
+        assert_eq!(alignments[0].score(), set.global_score());
+        alignments
+    }
+
+    #[test]
+    fn k_best_works() {
+        let x: Vec<char> = "GATTACA".chars().collect();
+        let y: Vec<char> = "GCATGCU".chars().collect();
+        let alignments = k_best(
+            "GATTACA",
+            "GCATGCU",
+            NeedlemanWunsch::new(1, -1, -1, -1),
+            10,
+        );
+        assert_eq!(alignments.len(), 10);
+        assert_eq!(alignments[0].score(), 0);
+        for pair in alignments.windows(2) {
+            assert!(pair[0].score() >= pair[1].score());
+        }
+        for alignment in alignments.iter() {
+            let score: isize = alignment
+                .steps()
+                .map(|step| match step {
+                    Step::Align { x: i, y: j } if x[i] == y[j] => 1,
+                    _ => -1,
+                })
+                .sum();
+            assert_eq!(score, alignment.score());
+            assert_eq!(alignment.end(), Cursor { x: 7, y: 7 });
+        }
+
+        // A single pair has three alignments: aligned, or two gaps in either order.
+        let alignments = k_best("A", "A", NeedlemanWunsch::new(1, -1, -1, -1), 10);
+        let scores: Vec<isize> = alignments.iter().map(Alignment::score).collect();
+        assert_eq!(scores, vec![1, -2, -2]);
+
+        let set = alignment_set("A", "A", NeedlemanWunsch::new(1, -1, -1, -1));
+        assert!(set.k_best(1).is_none());
+    }
+
+    #[test]
+    fn k_best_follows_the_strategy() {
+        // Terminal gaps are free, so the best alignment shifts y.
+        let strategy = NeedlemanWunsch::new(1, -1, -2, -2).with_terminal_gaps(0, 0);
+        let alignments = k_best("ACGT", "CGTA", strategy, 3);
+        assert_eq!(alignments[0].score(), 3);
+
+        // Free ends start and end anywhere along the first and last row.
+        let strategy = NeedlemanWunsch::new(1, -1, -1, -1)
+            .with_free_ends(FreeEnds::LEADING_X | FreeEnds::TRAILING_X);
+        let alignments = k_best("TTACGTT", "ACG", strategy, 4);
+        let scores: Vec<isize> = alignments.iter().map(Alignment::score).collect();
+        assert_eq!(&scores[..3], &[3, 2, 1]);
+        assert_eq!(alignments[0].origin(), &Cursor { x: 2, y: 0 });
+        assert_eq!(alignments[0].end(), Cursor { x: 5, y: 3 });
+
+        // Gaps cost by position.
+        let strategy = PositionalGaps::new(
+            NeedlemanWunsch::new(1, -1, -1, -1),
+            |x, _| if x == 0 { -5 } else { -1 },
+            |_, _| -1,
+        );
+        let alignments = k_best("AC", "C", strategy, 3);
+        let scores: Vec<isize> = alignments.iter().map(Alignment::score).collect();
+        assert_eq!(scores, vec![-2, -4, -7]);
+    }
+
+    #[test]
+    fn alignments_from_cells_work() {
+        let x: Vec<char> = "ACGTACGT".chars().collect();
+        let y: Vec<char> = "ACGT".chars().collect();
+        let strategy = NeedlemanWunsch::new(1, -1, -1, -1);
+        let set: AlignmentSet<InMemoryAlignmentMatrix> =
+            AlignmentSet::builder(x.len(), y.len(), strategy)
+                .with_scores_kept()
+                .build(|i, j| x[i] == y[j])
+                .unwrap();
+        let alignment = set.alignment_from(&Cursor { x: 4, y: 4 }).unwrap();
+        assert_eq!(alignment.score(), 4);
+        assert_eq!(alignment.origin(), &Cursor { x: 0, y: 0 });
+        assert!(alignment.steps().all(|step| step.mask() == StepMask::ALIGN));
+        assert_eq!(set.alignment_from(&Cursor { x: 0, y: 0 }).unwrap().len(), 0);
+        assert!(set.alignment_from(&Cursor { x: 9, y: 0 }).is_none());
+
+        let set = alignment_set("ACGTACGT", "ACGT", NeedlemanWunsch::new(1, -1, -1, -1));
+        assert!(set.alignments_from(&Cursor { x: 4, y: 4 }).is_none());
+    }
+
+    #[test]
+    fn co_optimal_alignments_work() {
+        // Both gaps of "AA" against "A" are optimal.
+        let strategy = NeedlemanWunsch::new(1, -1, -1, -1);
+        let set = alignment_set("AA", "A", strategy.clone());
+        assert_eq!(set.co_optimal_global_alignments().count(), 2);
+
+        // "AC" occurs twice in x.
+        let set = alignment_set("ACGTAC", "AC", SmithWaterman::new(2, -1, -1, -1));
+        let ends: Vec<_> = set
+            .co_optimal_local_alignments()
+            .map(|alignment| alignment.end())
+            .collect();
+        assert_eq!(ends.len(), 2);
+        assert!(ends.contains(&Cursor { x: 2, y: 2 }));
+        assert!(ends.contains(&Cursor { x: 6, y: 2 }));
+        assert_eq!(ends[0], *set.local_max());
+
+        let set = alignment_set("GGG", "TT", SmithWaterman::new(2, -1, -1, -1));
+        assert_eq!(set.co_optimal_local_alignments().count(), 1);
+    }
+
+    #[test]
+    fn co_optimal_alignments_are_counted() {
+        let strategy = NeedlemanWunsch::new(1, -1, -1, -1);
+        let set = alignment_set("AA", "A", strategy.clone());
+        assert_eq!(set.count_co_optimal_global_alignments(), 2);
+
+        let set = alignment_set("ACGTTGCAACGT", "AGTTCAAGT", strategy);
+        let count = set.co_optimal_global_alignments().count() as u64;
+        assert_eq!(set.count_co_optimal_global_alignments(), count);
+
+        let set = alignment_set("ACGTAC", "AC", SmithWaterman::new(2, -1, -1, -1));
+        assert_eq!(set.count_co_optimal_local_alignments(), 2);
+
+        // Every path through the matrix of two unrelated sequences is optimal.
+        let strategy = NeedlemanWunsch::new(1, 0, 0, 0);
+        let x = "A".repeat(40);
+        let y = "C".repeat(40);
+        let set = alignment_set(&x, &y, strategy);
+        assert_eq!(set.count_co_optimal_global_alignments(), u64::MAX);
+    }
+
+    #[test]
+    fn starts_work() {
+        let set = alignment_set("XXXABCXXX", "YYABCYY", SmithWaterman::new(2, -1, -1, -1));
+        assert_eq!(set.local_start(), Cursor { x: 3, y: 2 });
+        assert_eq!(&set.local_start(), set.local_alignment().origin());
+        assert_eq!(set.local_max(), &Cursor { x: 6, y: 5 });
+
+        let set = alignment_set("XXACGT", "ACGT", presets::semi_global(2, -1, -1, -1));
+        assert_eq!(&set.global_start(), set.global_alignment().origin());
+        // Aligning the second A or deleting it after the first is co-optimal.
+        let strategy =
+            NeedlemanWunsch::new(1, -1, 0, 0).with_boundaries(Boundary::Zero, Boundary::Gaps);
+        let set = alignment_set("AA", "A", strategy);
+        assert_eq!(set.global_start(), Cursor { x: 0, y: 0 });
+        let set = set.with_preference(Preference::GAPS_LEFT);
+        assert_eq!(set.global_start(), Cursor { x: 1, y: 0 });
+        assert_eq!(&set.global_start(), set.global_alignment().origin());
+    }
 }
@@ -3,11 +3,82 @@ use crate::pair::alignment_matrix::AlignmentMatrix;
 use crate::pair::cursor::Cursor;
 use crate::pair::step_mask::StepMask;
 
+// The order in which co-optimal steps into a cell get followed during the
+// traceback, the first one being part of the first alignment. As tracebacks
+// start at the end, preferring gaps moves them towards the end and vice versa.
+// Every order lists each of the four steps once, so that every step gets
+// followed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Preference([StepMask; 4]);
+
+impl Preference {
+    // Steps are followed in `order`, e.g. `[ALIGN, INSERT, DELETE,
+    // TRANSPOSE]`. None unless `order` lists each of ALIGN, DELETE, INSERT
+    // and TRANSPOSE once.
+    pub fn new(order: [StepMask; 4]) -> Option<Preference> {
+        let steps = [
+            StepMask::ALIGN,
+            StepMask::DELETE,
+            StepMask::INSERT,
+            StepMask::TRANSPOSE,
+        ];
+        if steps.iter().all(|step| order.contains(step)) {
+            Some(Preference(order))
+        } else {
+            None
+        }
+    }
+
+    pub const GAPS_RIGHT: Preference = Preference([
+        StepMask::TRANSPOSE,
+        StepMask::DELETE,
+        StepMask::INSERT,
+        StepMask::ALIGN,
+    ]);
+    pub const GAPS_LEFT: Preference = Preference([
+        StepMask::ALIGN,
+        StepMask::TRANSPOSE,
+        StepMask::DELETE,
+        StepMask::INSERT,
+    ]);
+
+    // The first of the steps in `mask` to follow, if any.
+    pub(crate) fn first(&self, mask: StepMask) -> Option<StepMask> {
+        self.0.iter().copied().find(|step| mask.contains(*step))
+    }
+
+    // The preference for a traceback over the reversed sequences which
+    // places gaps like `self` does over the sequences.
+    pub(crate) fn reversed(&self) -> Preference {
+        let mut order = self.0;
+        order.reverse();
+        Preference(order)
+    }
+
+    // Whether `step` gets followed before `other`.
+    pub(crate) fn prefers(&self, step: StepMask, other: StepMask) -> bool {
+        let rank = |step| {
+            self.0
+                .iter()
+                .position(|s| *s == step)
+                .unwrap_or(self.0.len())
+        };
+        rank(step) < rank(other)
+    }
+}
+
+impl Default for Preference {
+    fn default() -> Preference {
+        Preference::GAPS_RIGHT
+    }
+}
+
 pub struct Alignments<'a, T: 'a> {
     matrix: &'a T,
     stack: Vec<(StepMask, Cursor, usize)>,
     steps: Vec<StepMask>,
     score: isize,
+    preference: Preference,
 }
 
 impl<'a, T> Alignments<'a, T> {
@@ -22,8 +93,14 @@ impl<'a, T> Alignments<'a, T> {
             stack,
             steps,
             score,
+            preference: Preference::default(),
         }
     }
+
+    pub fn preferring(mut self, preference: Preference) -> Self {
+        self.preference = preference;
+        self
+    }
 }
 
 impl<'a, T> Alignments<'a, T>
@@ -36,7 +113,8 @@ where
         if steps == StepMask::STOP {
             return branches;
         }
-        for mask in [StepMask::ALIGN, StepMask::INSERT, StepMask::DELETE].iter() {
+        // Branches are taken from the back.
+        for mask in self.preference.0.iter().rev() {
             if steps.contains(*mask) {
                 let mut branch = cursor;
                 branch.apply_backwards_step(*mask);
@@ -54,22 +132,116 @@ where
     type Item = Alignment;
 
     fn next(&mut self) -> Option<Alignment> {
-        let zero = Cursor { x: 0, y: 0 };
         while let Some((step_mask, cursor, depth)) = self.stack.pop() {
             if step_mask != StepMask::STOP {
                 self.steps.truncate(depth - 1);
                 self.steps.push(step_mask);
             }
             let branches = self.branches(cursor);
-            for (step_mask, cursor) in branches {
-                self.stack.push((step_mask, cursor, depth + 1));
-            }
-            if ((step_mask == StepMask::STOP) && (depth > 0)) || (cursor == zero) {
+            if branches.is_empty() {
                 let mut steps: Vec<StepMask> = self.steps.clone();
                 steps.reverse();
                 return Some(Alignment::new(cursor, steps, self.score));
             }
+            for (step_mask, cursor) in branches {
+                self.stack.push((step_mask, cursor, depth + 1));
+            }
         }
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pair::alignment_matrix::in_memory::AlignmentMatrix as InMemoryAlignmentMatrix;
+    use crate::pair::alignment_set::AlignmentSet;
+    use crate::pair::needleman_wunsch::NeedlemanWunsch;
+
+    #[test]
+    fn preferences_work() {
+        let x = b"ACCGT";
+        let y = b"ACGT";
+        let strategy = NeedlemanWunsch::new(1, -1, -1, -1);
+        let set: AlignmentSet<InMemoryAlignmentMatrix> =
+            AlignmentSet::new(x.len(), y.len(), strategy, |i, j| x[i] == y[j]).unwrap();
+        let gap = |preference| {
+            let alignment = set
+                .global_alignments()
+                .preferring(preference)
+                .next()
+                .unwrap();
+            alignment
+                .steps()
+                .position(|step| step.mask() == StepMask::DELETE)
+        };
+        assert_eq!(gap(Preference::GAPS_RIGHT), Some(2));
+        assert_eq!(gap(Preference::GAPS_LEFT), Some(1));
+        assert_eq!(
+            set.global_alignments()
+                .preferring(Preference::GAPS_LEFT)
+                .count(),
+            2
+        );
+
+        // A preference of the set applies to all of its tracebacks.
+        let set = set.with_preference(Preference::GAPS_LEFT);
+        let position = |alignment: Alignment| {
+            alignment
+                .steps()
+                .position(|step| step.mask() == StepMask::DELETE)
+        };
+        assert_eq!(position(set.global_alignment()), Some(1));
+        assert_eq!(
+            position(set.co_optimal_global_alignments().next().unwrap()),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn custom_preferences_work() {
+        // Mismatching costs as much as a deletion and an insertion.
+        let strategy = NeedlemanWunsch::new(1, -2, -1, -1);
+        let set: AlignmentSet<InMemoryAlignmentMatrix> =
+            AlignmentSet::new(1, 1, strategy, |_, _| false).unwrap();
+        let masks = |preference| -> Vec<StepMask> {
+            let alignment = set
+                .global_alignments()
+                .preferring(preference)
+                .next()
+                .unwrap();
+            alignment.steps().map(|step| step.mask()).collect()
+        };
+        let insert_first = Preference::new([
+            StepMask::INSERT,
+            StepMask::DELETE,
+            StepMask::TRANSPOSE,
+            StepMask::ALIGN,
+        ])
+        .unwrap();
+        assert_eq!(
+            masks(insert_first),
+            vec![StepMask::DELETE, StepMask::INSERT]
+        );
+        assert_eq!(
+            masks(Preference::GAPS_RIGHT),
+            vec![StepMask::INSERT, StepMask::DELETE]
+        );
+        let align_first = Preference::new([
+            StepMask::ALIGN,
+            StepMask::INSERT,
+            StepMask::DELETE,
+            StepMask::TRANSPOSE,
+        ])
+        .unwrap();
+        assert_eq!(masks(align_first), vec![StepMask::ALIGN]);
+
+        let order = [
+            StepMask::ALIGN,
+            StepMask::ALIGN,
+            StepMask::DELETE,
+            StepMask::INSERT,
+        ];
+        assert_eq!(Preference::new(order), None);
+    }
+}
@@ -0,0 +1,134 @@
+use std::marker::PhantomData;
+
+use crate::pair::alignment::Alignment;
+use crate::pair::alignment_matrix::AlignmentMatrix;
+use crate::pair::alignment_set::{AlignmentSet, ScoreError};
+use crate::pair::cursor::Cursor;
+use crate::pair::step_mask::StepMask;
+use crate::pair::strategy::Strategy;
+
+#[derive(Debug)]
+pub enum AnchorError<E> {
+    // The anchor at `index` is out of bounds or not strictly after its predecessor.
+    InvalidAnchor { index: usize },
+    Score(ScoreError<E>),
+}
+
+// Global alignment whose path aligns every anchor pair `(x, y)`. Only the
+// rectangles between consecutive anchors get filled.
+#[derive(Clone, Debug)]
+pub struct Anchored<T, S> {
+    strategy: S,
+    matrix: PhantomData<T>,
+}
+
+impl<T, S, E> Anchored<T, S>
+where
+    T: AlignmentMatrix<Error = E>,
+    S: Strategy,
+{
+    pub fn new(strategy: S) -> Anchored<T, S> {
+        Anchored {
+            strategy,
+            matrix: PhantomData,
+        }
+    }
+
+    pub fn global_alignment<F>(
+        &self,
+        x_len: usize,
+        y_len: usize,
+        anchors: &[(usize, usize)],
+        f: F,
+    ) -> Result<Alignment, AnchorError<E>>
+    where
+        F: Fn(usize, usize) -> bool,
+    {
+        let mut start = Cursor { x: 0, y: 0 };
+        for (index, &(x, y)) in anchors.iter().enumerate() {
+            if x < start.x || y < start.y || x >= x_len || y >= y_len {
+                return Err(AnchorError::InvalidAnchor { index });
+            }
+            start = Cursor { x: x + 1, y: y + 1 };
+        }
+
+        let mut steps = vec![];
+        let mut score = 0;
+        let mut start = Cursor { x: 0, y: 0 };
+        let ends = anchors.iter().map(|&(x, y)| Cursor { x, y });
+        for end in ends.chain(Some(Cursor { x: x_len, y: y_len })) {
+            let set: AlignmentSet<T> = AlignmentSet::new(
+                end.x - start.x,
+                end.y - start.y,
+                self.strategy.clone(),
+                |x, y| f(start.x + x, start.y + y),
+            )
+            .map_err(AnchorError::Score)?;
+            let alignment = set.global_alignment();
+            steps.extend(alignment.steps().map(|step| step.mask()));
+            score += alignment.score();
+            if end.x < x_len {
+                steps.push(StepMask::ALIGN);
+                score += if f(end.x, end.y) {
+                    self.strategy.match_score()
+                } else {
+                    self.strategy.mismatch_score()
+                };
+            }
+            start = Cursor {
+                x: end.x + 1,
+                y: end.y + 1,
+            };
+        }
+        Ok(Alignment::new(Cursor { x: 0, y: 0 }, steps, score))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pair::alignment_matrix::in_memory::AlignmentMatrix as InMemoryAlignmentMatrix;
+    use crate::pair::needleman_wunsch::NeedlemanWunsch;
+    use crate::pair::step::Step;
+
+    fn align(x: &str, y: &str, anchors: &[(usize, usize)]) -> Alignment {
+        let x: Vec<char> = x.chars().collect();
+        let y: Vec<char> = y.chars().collect();
+        let anchored: Anchored<InMemoryAlignmentMatrix, _> =
+            Anchored::new(NeedlemanWunsch::new(1, -1, -1, -1));
+        anchored
+            .global_alignment(x.len(), y.len(), anchors, |i, j| x[i] == y[j])
+            .unwrap()
+    }
+
+    #[test]
+    fn anchors_are_aligned() {
+        let alignment = align("GATTACA", "GCATGCU", &[(3, 1), (5, 4)]);
+        let steps: Vec<Step> = alignment.steps().collect();
+        assert!(steps.contains(&Step::Align { x: 3, y: 1 }));
+        assert!(steps.contains(&Step::Align { x: 5, y: 4 }));
+        assert_eq!(alignment.end(), Cursor { x: 7, y: 7 });
+    }
+
+    #[test]
+    fn no_anchors_is_global_alignment() {
+        let alignment = align("GATTACA", "GCATGCU", &[]);
+        assert_eq!(alignment.score(), 0);
+    }
+
+    #[test]
+    fn invalid_anchors_are_rejected() {
+        let anchored: Anchored<InMemoryAlignmentMatrix, _> =
+            Anchored::new(NeedlemanWunsch::new(1, -1, -1, -1));
+        let result = anchored.global_alignment(4, 4, &[(1, 1), (1, 2)], |_, _| true);
+        assert!(matches!(
+            result,
+            Err(AnchorError::InvalidAnchor { index: 1 })
+        ));
+        let result = anchored.global_alignment(4, 4, &[(4, 0)], |_, _| true);
+        assert!(matches!(
+            result,
+            Err(AnchorError::InvalidAnchor { index: 0 })
+        ));
+    }
+}
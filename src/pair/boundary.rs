@@ -0,0 +1,23 @@
+use crate::pair::free_ends::FreeEnds;
+
+// How the cells of the first row or column of a matrix get initialized.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Boundary {
+    // Leading gaps get scored like any other gap.
+    Gaps,
+    // Leading gaps are free, so alignments may start anywhere along it.
+    Zero,
+    // Leading gaps are not allowed at all.
+    Blocked,
+}
+
+impl Boundary {
+    // `Zero` if the leading `end` is free in `free_ends`, `Gaps` otherwise.
+    pub fn of(free_ends: FreeEnds, end: FreeEnds) -> Boundary {
+        if free_ends.contains(end) {
+            Boundary::Zero
+        } else {
+            Boundary::Gaps
+        }
+    }
+}
@@ -0,0 +1,96 @@
+// Case-insensitive comparison of characters, leaving the sequences (and so
+// the aligned output) untouched.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum CaseFolding {
+    // Only ASCII letters are folded.
+    Ascii,
+    // Unicode simple case folding, e.g. `Σ`, `σ` and the final `ς` all match.
+    // Folds expanding to several characters, like `ß` to `ss`, are not
+    // applied, so `ß` only matches `ẞ`.
+    Unicode,
+}
+
+// The simple case folding of `c` is the lowercase mapping of what this
+// returns. It only differs from `c` for lowercase variants which fold to
+// another lowercase letter.
+fn fold_variant(c: char) -> char {
+    match c {
+        'ς' => 'σ',
+        'ϐ' => 'β',
+        'ϑ' => 'θ',
+        'ϕ' => 'φ',
+        'ϖ' => 'π',
+        'ϰ' => 'κ',
+        'ϱ' => 'ρ',
+        'ϵ' => 'ε',
+        'ſ' => 's',
+        'ẛ' => 'ṡ',
+        '\u{0345}' | '\u{1FBE}' => 'ι',
+        '\u{1C80}' => 'в',
+        '\u{1C81}' => 'д',
+        '\u{1C82}' => 'о',
+        '\u{1C83}' => 'с',
+        '\u{1C84}' | '\u{1C85}' => 'т',
+        '\u{1C86}' => 'ъ',
+        '\u{1C87}' => 'ѣ',
+        '\u{1C88}' => 'ꙋ',
+        c => c,
+    }
+}
+
+impl CaseFolding {
+    pub fn matches(self, a: char, b: char) -> bool {
+        match self {
+            CaseFolding::Ascii => a.eq_ignore_ascii_case(&b),
+            CaseFolding::Unicode => {
+                a == b
+                    || fold_variant(a)
+                        .to_lowercase()
+                        .eq(fold_variant(b).to_lowercase())
+            }
+        }
+    }
+
+    // Whether x[i] and y[j] are equal up to case under this folding.
+    pub fn matcher<'a>(self, x: &'a [char], y: &'a [char]) -> impl Fn(usize, usize) -> bool + 'a {
+        move |i, j| self.matches(x[i], y[j])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pair::alignment_matrix::in_memory::AlignmentMatrix as InMemoryAlignmentMatrix;
+    use crate::pair::alignment_set::AlignmentSet;
+    use crate::pair::needleman_wunsch::NeedlemanWunsch;
+
+    #[test]
+    fn case_is_ignored() {
+        let x: Vec<char> = "Straße ΣΟΦΙΑ".chars().collect();
+        let y: Vec<char> = "STRAßE σοφια".chars().collect();
+        let strategy = NeedlemanWunsch::new(1, -1, -1, -1);
+        let score = |folding: CaseFolding| {
+            let set: AlignmentSet<InMemoryAlignmentMatrix> =
+                AlignmentSet::new(x.len(), y.len(), strategy.clone(), folding.matcher(&x, &y))
+                    .unwrap();
+            set.global_score()
+        };
+        assert_eq!(score(CaseFolding::Ascii), 7 - 5);
+        assert_eq!(score(CaseFolding::Unicode), 12);
+        assert!(!CaseFolding::Ascii.matches('σ', 'Σ'));
+    }
+
+    #[test]
+    fn variants_fold_together() {
+        let unicode = |a, b| CaseFolding::Unicode.matches(a, b);
+        assert!(unicode('ς', 'σ'));
+        assert!(unicode('ς', 'Σ'));
+        assert!(unicode('ſ', 'S'));
+        assert!(unicode('ϑ', 'Θ'));
+        assert!(unicode('ß', 'ẞ'));
+        assert!(unicode('K', '\u{212A}'));
+        assert!(!unicode('ı', 'i'));
+        assert!(!unicode('İ', 'i'));
+        assert!(!CaseFolding::Ascii.matches('ς', 'σ'));
+    }
+}
@@ -0,0 +1,222 @@
+use std::cmp;
+use std::marker::PhantomData;
+
+use crate::pair::alignment::Alignment;
+use crate::pair::alignment_matrix::AlignmentMatrix;
+use crate::pair::alignment_set::MIN_SCORE;
+use crate::pair::alignments::Preference;
+use crate::pair::cursor::Cursor;
+use crate::pair::step_mask::StepMask;
+use crate::pair::strategy::Strategy;
+
+// Global alignment keeping only the scores of every `interval`-th row plus
+// the last one. The traceback re-fills the block of rows between two
+// checkpoints into a matrix of `interval + 1` rows at a time, so memory is
+// O(x_len * y_len / interval) at the cost of computing each cell twice.
+// Bands, free ends and circular strategies are not supported.
+pub struct CheckpointedAlignmentSet<T, S, F> {
+    x_len: usize,
+    y_len: usize,
+    strategy: S,
+    f: F,
+    interval: usize,
+    checkpoints: Vec<Vec<isize>>,
+    preference: Preference,
+    matrix: PhantomData<T>,
+}
+
+impl<T, S, F, E> CheckpointedAlignmentSet<T, S, F>
+where
+    T: AlignmentMatrix<Error = E>,
+    S: Strategy,
+    F: Fn(usize, usize) -> bool,
+{
+    pub fn new(
+        x_len: usize,
+        y_len: usize,
+        strategy: S,
+        interval: usize,
+        f: F,
+    ) -> CheckpointedAlignmentSet<T, S, F> {
+        let interval = cmp::max(interval, 1);
+        let mut set = CheckpointedAlignmentSet {
+            x_len,
+            y_len,
+            strategy,
+            f,
+            interval,
+            checkpoints: vec![],
+            preference: Preference::default(),
+            matrix: PhantomData,
+        };
+        let mut row = set.first_row(None);
+        let mut y = 0;
+        while y < y_len {
+            let end = cmp::min(y + interval, y_len);
+            let next = set.fill_rows(&row, y, end, None);
+            set.checkpoints.push(row);
+            row = next;
+            y = end;
+        }
+        set.checkpoints.push(row);
+        set
+    }
+
+    fn first_row(&self, mut matrix: Option<&mut T>) -> Vec<isize> {
+        let mut row = vec![0; self.x_len + 1];
+        for x in 1..=self.x_len {
+            let score = row[x - 1] + self.strategy.terminal_delete_score_at(x - 1, 0);
+            row[x] = self.strategy.total_score(score);
+            if let Some(matrix) = matrix.as_mut() {
+                let steps = self.strategy.step_mask(MIN_SCORE, MIN_SCORE, score);
+                matrix.set_at(&Cursor { x, y: 0 }, steps);
+            }
+        }
+        row
+    }
+
+    // Computes the rows `start + 1..=end` from row `start`, recording their
+    // steps in `matrix` relative to row `start` if given. Returns row `end`.
+    fn fill_rows(
+        &self,
+        from: &[isize],
+        start: usize,
+        end: usize,
+        mut matrix: Option<&mut T>,
+    ) -> Vec<isize> {
+        let strategy = &self.strategy;
+        let mut row = from.to_vec();
+        for y in start..end {
+            let mut last_diagonal = row[0];
+            let insert = row[0] + strategy.terminal_insert_score_at(0, y);
+            row[0] = strategy.total_score(insert);
+            if let Some(matrix) = matrix.as_mut() {
+                let steps = strategy.step_mask(MIN_SCORE, insert, MIN_SCORE);
+                matrix.set_at(
+                    &Cursor {
+                        x: 0,
+                        y: y + 1 - start,
+                    },
+                    steps,
+                );
+            }
+            for x in 0..self.x_len {
+                let substitution = if (self.f)(x, y) {
+                    strategy.match_score()
+                } else {
+                    strategy.mismatch_score()
+                };
+                let delete = if y + 1 == self.y_len {
+                    strategy.terminal_delete_score_at(x, y + 1)
+                } else {
+                    strategy.delete_score_at(x, y + 1)
+                };
+                let insert = if x + 1 == self.x_len {
+                    strategy.terminal_insert_score_at(x + 1, y)
+                } else {
+                    strategy.insert_score_at(x + 1, y)
+                };
+                let align = last_diagonal.saturating_add(substitution);
+                let delete = row[x].saturating_add(delete);
+                let insert = row[x + 1].saturating_add(insert);
+                if let Some(matrix) = matrix.as_mut() {
+                    let cursor = Cursor {
+                        x: x + 1,
+                        y: y + 1 - start,
+                    };
+                    matrix.set_at(&cursor, strategy.step_mask(align, insert, delete));
+                }
+                last_diagonal = row[x + 1];
+                row[x + 1] = strategy.total_score(cmp::max(cmp::max(align, delete), insert));
+            }
+        }
+        row
+    }
+
+    pub fn global_score(&self) -> isize {
+        self.checkpoints.last().unwrap()[self.x_len]
+    }
+
+    // Like `AlignmentSet::with_preference`.
+    pub fn with_preference(mut self, preference: Preference) -> CheckpointedAlignmentSet<T, S, F> {
+        self.preference = preference;
+        self
+    }
+
+    // Prefers the same steps as `AlignmentSet::global_alignment`.
+    pub fn global_alignment(&self) -> Result<Alignment, E> {
+        let mut cursor = Cursor {
+            x: self.x_len,
+            y: self.y_len,
+        };
+        let mut steps = vec![];
+        'blocks: for block in (0..self.checkpoints.len() - 1).rev() {
+            let start = block * self.interval;
+            let end = cmp::min(start + self.interval, self.y_len);
+            let mut matrix = T::new(self.x_len + 1, end - start + 1)?;
+            if start == 0 {
+                self.first_row(Some(&mut matrix));
+            }
+            self.fill_rows(&self.checkpoints[block], start, end, Some(&mut matrix));
+            while cursor.y > start || (start == 0 && cursor.x > 0) {
+                let local = Cursor {
+                    x: cursor.x,
+                    y: cursor.y - start,
+                };
+                let step = match self.preference.first(matrix.at(&local)) {
+                    Some(step) => step,
+                    None => break 'blocks,
+                };
+                steps.push(step);
+                cursor.apply_backwards_step(step);
+            }
+        }
+        if self.y_len == 0 {
+            steps = vec![StepMask::DELETE; self.x_len];
+            cursor.x = 0;
+        }
+        steps.reverse();
+        Ok(Alignment::new(cursor, steps, self.global_score()))
+    }
+
+    // The number of score rows kept.
+    pub fn checkpoints(&self) -> usize {
+        self.checkpoints.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pair::alignment_matrix::in_memory::AlignmentMatrix as InMemoryAlignmentMatrix;
+    use crate::pair::alignment_set::AlignmentSet;
+    use crate::pair::needleman_wunsch::NeedlemanWunsch;
+
+    #[test]
+    fn matches_full_matrix() {
+        let x: Vec<char> = "The quick brown fox jumps over the lazy dog."
+            .chars()
+            .collect();
+        let y: Vec<char> = "The brown dog jumps over the very lazy snail."
+            .chars()
+            .collect();
+        let strategy = NeedlemanWunsch::new(1, -1, -1, -1);
+        let full: AlignmentSet<InMemoryAlignmentMatrix> =
+            AlignmentSet::new(x.len(), y.len(), strategy.clone(), |i, j| x[i] == y[j]).unwrap();
+        let expected: Vec<_> = full.global_alignment().steps().collect();
+        for interval in [1, 4, 7, 45, 100] {
+            let set: CheckpointedAlignmentSet<InMemoryAlignmentMatrix, _, _> =
+                CheckpointedAlignmentSet::new(
+                    x.len(),
+                    y.len(),
+                    strategy.clone(),
+                    interval,
+                    |i, j| x[i] == y[j],
+                );
+            assert_eq!(set.global_score(), full.global_score());
+            let alignment = set.global_alignment().unwrap();
+            assert_eq!(alignment.steps().collect::<Vec<_>>(), expected);
+            assert_eq!(set.checkpoints(), y.len().div_ceil(interval) + 1);
+        }
+    }
+}
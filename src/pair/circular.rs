@@ -0,0 +1,257 @@
+use std::cmp;
+
+use crate::pair::alignment_set::{boundary_cell, calculate_cell, ScoreError, MIN_SCORE};
+use crate::pair::boundary::Boundary;
+use crate::pair::cursor::Cursor;
+use crate::pair::free_ends::FreeEnds;
+use crate::pair::step_mask::StepMask;
+use crate::pair::strategy::Strategy;
+
+// The best rotation of a circular x (Maes, 1990). With x doubled, aligning
+// rotation r means finding the best path from (r, 0) to (r + x_len, y_len),
+// i.e. a path constrained to span exactly x_len columns. Optimal paths of
+// different rotations need not cross, so a rotation between two others has
+// an optimal path in the corridor between theirs. Bisecting the rotations
+// fills O(log x_len) layers of corridors of O(x_len * y_len) cells each,
+// instead of one matrix per rotation.
+//
+// This relies on no score depending on where a rotation starts, see
+// `applies`.
+
+// The cells of a path per row of the doubled matrix, as the first and last
+// column it occupies.
+struct Path {
+    columns: Vec<(usize, usize)>,
+}
+
+impl Path {
+    fn shifted(&self, offset: usize) -> Path {
+        let columns = self
+            .columns
+            .iter()
+            .map(|&(first, last)| (first + offset, last + offset))
+            .collect();
+        Path { columns }
+    }
+}
+
+// Whether the first and last column of a rotation score like any other, so
+// that paths of different rotations can be swapped piecewise. Otherwise all
+// rotations have to be filled.
+pub(crate) fn applies<S: Strategy>(strategy: &S, x_len: usize, y_len: usize) -> bool {
+    // Local scores restart paths anywhere.
+    strategy.total_score(MIN_SCORE) == MIN_SCORE
+        && strategy.bandwidth().is_none()
+        && strategy.first_column() == Boundary::Gaps
+        && !strategy.free_ends().contains(FreeEnds::TRAILING_Y)
+        && (0..x_len).all(|x| {
+            (0..y_len)
+                .all(|y| strategy.terminal_insert_score_at(x, y) == strategy.insert_score_at(x, y))
+        })
+}
+
+// Of rotations sharing the best global score the first one wins.
+pub(crate) fn best_rotation<S, F, E>(
+    strategy: &S,
+    x_len: usize,
+    y_len: usize,
+    substitution: &F,
+    checked: bool,
+) -> Result<usize, ScoreError<E>>
+where
+    S: Strategy,
+    F: Fn(usize, usize) -> isize,
+{
+    let search = Search {
+        strategy,
+        substitution,
+        x_len,
+        y_len,
+        checked,
+    };
+    let columns = vec![(0, x_len); y_len + 1];
+    let (score, first) = search.align(0, &columns)?;
+    let last = first.shifted(x_len);
+    let mut best = (score, 0);
+    search.bisect((0, &first), (x_len, &last), &mut best)?;
+    Ok(best.1)
+}
+
+struct Search<'a, S, F> {
+    strategy: &'a S,
+    substitution: &'a F,
+    x_len: usize,
+    y_len: usize,
+    checked: bool,
+}
+
+impl<'a, S, F> Search<'a, S, F>
+where
+    S: Strategy,
+    F: Fn(usize, usize) -> isize,
+{
+    // Aligns the rotations strictly between `left` and `right` within the
+    // corridor bounded by their paths.
+    fn bisect<E>(
+        &self,
+        left: (usize, &Path),
+        right: (usize, &Path),
+        best: &mut (isize, usize),
+    ) -> Result<(), ScoreError<E>> {
+        if right.0 - left.0 < 2 {
+            return Ok(());
+        }
+        let rotation = left.0 + (right.0 - left.0) / 2;
+        let columns: Vec<(usize, usize)> = left
+            .1
+            .columns
+            .iter()
+            .zip(right.1.columns.iter())
+            .map(|(&(first, _), &(_, last))| {
+                let first = cmp::max(first, rotation);
+                let last = cmp::min(last, rotation + self.x_len);
+                (first, cmp::max(first, last))
+            })
+            .collect();
+        let (score, path) = self.align(rotation, &columns)?;
+        if score > best.0 || (score == best.0 && rotation < best.1) {
+            *best = (score, rotation);
+        }
+        self.bisect(left, (rotation, &path), best)?;
+        self.bisect((rotation, &path), right, best)
+    }
+
+    // The global score of the rotation and an optimal path of it, filling
+    // only the given columns of each row. Cells are scored like in
+    // `AlignmentSet::fill_rotation`.
+    fn align<E>(
+        &self,
+        rotation: usize,
+        columns: &[(usize, usize)],
+    ) -> Result<(isize, Path), ScoreError<E>> {
+        let strategy = self.strategy;
+        let (x_len, y_len) = (self.x_len, self.y_len);
+        let at = |x: usize| x % x_len;
+        let overflow = |x: usize, y: usize| ScoreError::Overflow {
+            cursor: Cursor { x: x - rotation, y },
+        };
+
+        let mut steps: Vec<Vec<StepMask>> = Vec::with_capacity(y_len + 1);
+        let (first, last) = columns[0];
+        let mut row: Vec<isize> = Vec::with_capacity(last - first + 1);
+        let mut row_steps = Vec::with_capacity(last - first + 1);
+        for x in first..=last {
+            let (step, score) = if x == rotation {
+                (StepMask::STOP, 0)
+            } else {
+                let previous = if x > first {
+                    row[x - 1 - first]
+                } else {
+                    MIN_SCORE
+                };
+                let gap = strategy.terminal_delete_score_at(at(x - 1), 0);
+                let boundary = strategy.first_row();
+                boundary_cell(
+                    strategy,
+                    previous,
+                    StepMask::DELETE,
+                    gap,
+                    boundary,
+                    self.checked,
+                )
+                .ok_or_else(|| overflow(x, 0))?
+            };
+            row.push(score);
+            row_steps.push(step);
+        }
+        steps.push(row_steps);
+
+        for y in 1..=y_len {
+            let (above_first, above_last) = columns[y - 1];
+            let above = |x: usize| {
+                if x >= above_first && x <= above_last {
+                    row[x - above_first]
+                } else {
+                    MIN_SCORE
+                }
+            };
+            let (first, last) = columns[y];
+            let mut next: Vec<isize> = Vec::with_capacity(last - first + 1);
+            let mut row_steps = Vec::with_capacity(last - first + 1);
+            for x in first..=last {
+                let (step, score) = if x == rotation {
+                    let gap = strategy.terminal_insert_score_at(at(x), y - 1);
+                    let boundary = strategy.first_column();
+                    boundary_cell(
+                        strategy,
+                        above(x),
+                        StepMask::INSERT,
+                        gap,
+                        boundary,
+                        self.checked,
+                    )
+                } else {
+                    let before = if x > first {
+                        next[x - 1 - first]
+                    } else {
+                        MIN_SCORE
+                    };
+                    let previous = (above(x - 1), before, above(x));
+                    let gaps = (
+                        if y == y_len {
+                            strategy.terminal_delete_score_at(at(x - 1), y)
+                        } else {
+                            strategy.delete_score_at(at(x - 1), y)
+                        },
+                        if x - rotation == x_len {
+                            strategy.terminal_insert_score_at(at(x), y - 1)
+                        } else {
+                            strategy.insert_score_at(at(x), y - 1)
+                        },
+                    );
+                    let substitution = (self.substitution)(at(x - 1), y - 1);
+                    calculate_cell(strategy, previous, substitution, gaps, self.checked)
+                }
+                .ok_or_else(|| overflow(x, y))?;
+                next.push(score);
+                row_steps.push(step);
+            }
+            steps.push(row_steps);
+            row = next;
+        }
+
+        // Trailing gaps in x are free by ending early in the last row.
+        let (first, last) = columns[y_len];
+        let mut end = rotation + x_len;
+        if strategy.free_ends().contains(FreeEnds::TRAILING_X) {
+            for x in first..=last {
+                if row[x - first] > row[end - first] {
+                    end = x;
+                }
+            }
+        }
+        let score = row[end - first];
+
+        let mut path = vec![(usize::MAX, 0); y_len + 1];
+        let mut cursor = Cursor { x: end, y: y_len };
+        loop {
+            let (first, last) = &mut path[cursor.y];
+            *first = cmp::min(*first, cursor.x);
+            *last = cmp::max(*last, cursor.x);
+            let mask = steps[cursor.y][cursor.x - columns[cursor.y].0];
+            let step = [StepMask::ALIGN, StepMask::DELETE, StepMask::INSERT]
+                .iter()
+                .copied()
+                .find(|step| mask.contains(*step));
+            match step {
+                Some(step) => cursor.apply_backwards_step(step),
+                None => break,
+            }
+        }
+        // A path which ended or started early along the last or first row
+        // still spans the rotation there, at no cost.
+        path[0].0 = rotation;
+        path[y_len].1 = rotation + x_len;
+        Ok((score, Path { columns: path }))
+    }
+}
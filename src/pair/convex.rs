@@ -0,0 +1,35 @@
+use crate::pair::strategy::{GapModel, GapStrategy};
+
+// Scores gaps by the model `gap`, e.g. `|n| -4 - (2.0 * (n as f64).ln()) as isize`.
+#[derive(Clone, Debug)]
+pub struct Convex<G> {
+    pub equal: isize,
+    pub align: isize,
+    gap: G,
+}
+
+impl<G> Convex<G>
+where
+    G: GapModel,
+{
+    pub fn new(equal: isize, align: isize, gap: G) -> Convex<G> {
+        Convex { equal, align, gap }
+    }
+}
+
+impl<G> GapStrategy for Convex<G>
+where
+    G: GapModel,
+{
+    fn match_score(&self) -> isize {
+        self.equal
+    }
+
+    fn mismatch_score(&self) -> isize {
+        self.align
+    }
+
+    fn gap_score(&self, len: usize) -> isize {
+        self.gap.gap_score(len)
+    }
+}
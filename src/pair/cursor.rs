@@ -20,6 +20,7 @@ impl Cursor {
             StepMask::ALIGN => (1, 1),
             StepMask::INSERT => (0, 1),
             StepMask::DELETE => (1, 0),
+            StepMask::TRANSPOSE => (2, 2),
             StepMask::STOP => (0, 0),
             _ => {
                 panic!("Invalid step_mask.");
@@ -0,0 +1,111 @@
+use crate::pair::alignment::Alignment;
+use crate::pair::alignments::Preference;
+use crate::pair::score_table;
+use crate::pair::step_mask::StepMask;
+
+// Global alignment which may swap two adjacent elements in a single
+// transposition step. Transposed elements are not edited any further
+// (optimal string alignment distance).
+#[derive(Clone, Debug)]
+pub struct DamerauLevenshtein {
+    pub equal: isize,
+    pub align: isize,
+    pub insert: isize,
+    pub delete: isize,
+    pub transpose: isize,
+    // The order in which co-optimal steps into a cell are taken.
+    pub preference: Preference,
+}
+
+impl DamerauLevenshtein {
+    pub fn new(
+        equal: isize,
+        align: isize,
+        insert: isize,
+        delete: isize,
+        transpose: isize,
+    ) -> DamerauLevenshtein {
+        DamerauLevenshtein {
+            equal,
+            align,
+            insert,
+            delete,
+            transpose,
+            preference: Preference::default(),
+        }
+    }
+
+    pub fn with_preference(mut self, preference: Preference) -> DamerauLevenshtein {
+        self.preference = preference;
+        self
+    }
+
+    pub fn global_alignment<F>(&self, x_len: usize, y_len: usize, f: F) -> Alignment
+    where
+        F: Fn(usize, usize) -> bool,
+    {
+        score_table::global_alignment(x_len, y_len, &self.preference, |x, y, candidate| {
+            if x > 0 && y > 0 {
+                let score = if f(x - 1, y - 1) {
+                    self.equal
+                } else {
+                    self.align
+                };
+                candidate(score, StepMask::ALIGN, 1);
+            }
+            if x > 0 {
+                candidate(self.delete, StepMask::DELETE, 1);
+            }
+            if y > 0 {
+                candidate(self.insert, StepMask::INSERT, 1);
+            }
+            if x > 1 && y > 1 && f(x - 1, y - 2) && f(x - 2, y - 1) && !f(x - 1, y - 1) {
+                candidate(self.transpose, StepMask::TRANSPOSE, 1);
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pair::cursor::Cursor;
+    use crate::pair::step::Step;
+
+    fn align(x: &str, y: &str) -> Alignment {
+        let x: Vec<char> = x.chars().collect();
+        let y: Vec<char> = y.chars().collect();
+        let strategy = DamerauLevenshtein::new(0, -1, -1, -1, -1);
+        strategy.global_alignment(x.len(), y.len(), |i, j| x[i] == y[j])
+    }
+
+    #[test]
+    fn transpositions_work() {
+        let alignment = align("abcd", "acbd");
+        assert_eq!(alignment.score(), -1);
+        let steps: Vec<Step> = alignment.steps().collect();
+        assert_eq!(
+            steps,
+            vec![
+                Step::Align { x: 0, y: 0 },
+                Step::Transpose { x: 1, y: 1 },
+                Step::Align { x: 3, y: 3 },
+            ]
+        );
+        assert_eq!(alignment.end(), Cursor { x: 4, y: 4 });
+    }
+
+    #[test]
+    fn transposed_elements_are_not_edited_again() {
+        assert_eq!(align("ca", "abc").score(), -3);
+        assert_eq!(align("kitten", "sitting").score(), -3);
+    }
+
+    #[test]
+    fn huge_gap_scores_saturate() {
+        let x: Vec<char> = "abcd".chars().collect();
+        let strategy = DamerauLevenshtein::new(1, -1, isize::MIN / 2, isize::MIN / 2, -1);
+        let alignment = strategy.global_alignment(x.len(), x.len(), |i, j| x[i] == x[j]);
+        assert_eq!(alignment.score(), 4);
+    }
+}
@@ -0,0 +1,80 @@
+// An edit of x towards y. `pos` is the position in x the edit starts at,
+// before any edit got applied, and `source` that of the elements in y
+// inserted or substituted for those of x.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Edit {
+    Insert {
+        pos: usize,
+        len: usize,
+        source: usize,
+    },
+    Delete {
+        pos: usize,
+        len: usize,
+    },
+    Substitute {
+        pos: usize,
+        len: usize,
+        source: usize,
+    },
+}
+
+impl Edit {
+    fn extend(&mut self, next: &Edit) -> bool {
+        match (self, next) {
+            (
+                Edit::Insert { pos, len, source },
+                Edit::Insert {
+                    pos: next_pos,
+                    source: next_source,
+                    ..
+                },
+            ) if pos == next_pos && *source + *len == *next_source => *len += 1,
+            (Edit::Delete { pos, len }, Edit::Delete { pos: next_pos, .. })
+                if *pos + *len == *next_pos =>
+            {
+                *len += 1
+            }
+            (
+                Edit::Substitute { pos, len, source },
+                Edit::Substitute {
+                    pos: next_pos,
+                    source: next_source,
+                    ..
+                },
+            ) if *pos + *len == *next_pos && *source + *len == *next_source => *len += 1,
+            _ => return false,
+        }
+        true
+    }
+}
+
+// Merges single-element edits into runs.
+pub(crate) fn merged(edits: impl Iterator<Item = Edit>) -> Vec<Edit> {
+    let mut merged: Vec<Edit> = vec![];
+    for edit in edits {
+        if !merged.last_mut().is_some_and(|last| last.extend(&edit)) {
+            merged.push(edit);
+        }
+    }
+    merged
+}
+
+// Applies `edits` of x towards y, ordered by position as returned by
+// `Alignment::edits`.
+pub fn apply<T: Clone>(edits: &[Edit], x: &[T], y: &[T]) -> Vec<T> {
+    let mut result = vec![];
+    let mut position = 0;
+    for edit in edits {
+        let (pos, skip, insert) = match *edit {
+            Edit::Insert { pos, len, source } => (pos, 0, source..source + len),
+            Edit::Delete { pos, len } => (pos, len, 0..0),
+            Edit::Substitute { pos, len, source } => (pos, len, source..source + len),
+        };
+        result.extend_from_slice(&x[position..pos]);
+        result.extend_from_slice(&y[insert]);
+        position = pos + skip;
+    }
+    result.extend_from_slice(&x[position..]);
+    result
+}
@@ -0,0 +1,369 @@
+use std::fmt::Display;
+
+use crate::pair::alignment::{Alignment, Reference};
+use crate::pair::step::Step;
+
+// The aligned pairs of elements of x and y, with `None` for gaps.
+fn columns<'a, T>(
+    alignment: &Alignment,
+    x: &'a [T],
+    y: &'a [T],
+) -> Vec<(Option<&'a T>, Option<&'a T>)> {
+    alignment
+        .pairs()
+        .map(|(i, j)| (i.map(|i| &x[i]), j.map(|j| &y[j])))
+        .collect()
+}
+
+fn markup<T: PartialEq>(column: &(Option<&T>, Option<&T>)) -> char {
+    match column {
+        (Some(a), Some(b)) if a == b => '|',
+        (Some(_), Some(_)) => '.',
+        _ => ' ',
+    }
+}
+
+fn gapped<T: Display>(element: Option<&T>) -> String {
+    element.map_or("-".to_string(), |element| element.to_string())
+}
+
+// The aligned regions of x and y with `gap` for gaps, e.g. to compare them
+// column by column.
+pub fn gapped_strings(alignment: &Alignment, x: &str, y: &str, gap: char) -> (String, String) {
+    let (x, y): (Vec<char>, Vec<char>) = (x.chars().collect(), y.chars().collect());
+    let (x, y) = alignment.gapped(&x, &y);
+    let string = |elements: Vec<Option<char>>| -> String {
+        elements.into_iter().map(|c| c.unwrap_or(gap)).collect()
+    };
+    (string(x), string(y))
+}
+
+// Renders the aligned parts of x and y above one another, with a middle
+// line of `|` for equal and `.` for unequal aligned elements, and `-` for
+// gaps. Lines are wrapped every `width` columns, with blocks separated by
+// an empty line. Elements are expected to render as a single character.
+pub fn pretty<T>(alignment: &Alignment, x: &[T], y: &[T], width: usize) -> String
+where
+    T: Display + PartialEq,
+{
+    let columns = columns(alignment, x, y);
+    let mut blocks = vec![];
+    for block in columns.chunks(width.max(1)) {
+        let mut lines = [String::new(), String::new(), String::new()];
+        for column in block {
+            lines[0].push_str(&gapped(column.0));
+            lines[1].push(markup(column));
+            lines[2].push_str(&gapped(column.1));
+        }
+        blocks.push(lines.join("\n"));
+    }
+    blocks.join("\n\n")
+}
+
+// The alignment in the pair format of EMBOSS' `needle` and `water`, with x
+// and y named by `names`.
+pub fn emboss(alignment: &Alignment, x: &str, y: &str, names: (&str, &str)) -> String {
+    let (x, y): (Vec<char>, Vec<char>) = (x.chars().collect(), y.chars().collect());
+    let columns = columns(alignment, &x, &y);
+    let length = columns.len();
+    let identity = columns
+        .iter()
+        .filter(|column| markup(column) == '|')
+        .count();
+    let gaps = columns
+        .iter()
+        .filter(|column| markup(column) == ' ')
+        .count();
+    let percent = |count: usize| 100.0 * count as f64 / length.max(1) as f64;
+    let mut output = String::new();
+    output.push_str("#=======================================\n#\n");
+    output.push_str(&format!("# 1: {}\n# 2: {}\n", names.0, names.1));
+    output.push_str(&format!("# Length: {}\n", length));
+    output.push_str(&format!(
+        "# Identity: {}/{} ({:.1}%)\n",
+        identity,
+        length,
+        percent(identity)
+    ));
+    // Without a substitution matrix only identical elements are similar.
+    output.push_str(&format!(
+        "# Similarity: {}/{} ({:.1}%)\n",
+        identity,
+        length,
+        percent(identity)
+    ));
+    output.push_str(&format!(
+        "# Gaps: {}/{} ({:.1}%)\n",
+        gaps,
+        length,
+        percent(gaps)
+    ));
+    output.push_str(&format!("# Score: {}\n", alignment.score()));
+    output.push_str("#\n#=======================================\n");
+
+    let name = |name: &str| name.chars().take(13).collect::<String>();
+    let mut positions = (alignment.origin().x, alignment.origin().y);
+    // Rows of a block show the 1-based positions of their first and last
+    // element in the sequence.
+    let row = |name: &str, position: &mut usize, elements: Vec<Option<&char>>| {
+        let count = elements.iter().filter(|element| element.is_some()).count();
+        let start = if count > 0 { *position + 1 } else { *position };
+        *position += count;
+        let text: String = elements.into_iter().map(gapped).collect();
+        format!("{:<13} {:>6} {} {:>6}\n", name, start, text, *position)
+    };
+    for block in columns.chunks(50) {
+        output.push('\n');
+        let top = block.iter().map(|column| column.0).collect();
+        output.push_str(&row(&name(names.0), &mut positions.0, top));
+        let markup: String = block.iter().map(markup).collect();
+        output.push_str(&format!("{:21}{}\n", "", markup));
+        let bottom = block.iter().map(|column| column.1).collect();
+        output.push_str(&row(&name(names.1), &mut positions.1, bottom));
+    }
+    output
+}
+
+// The alignment as a MAF block, with x and y named by `names`. Positions
+// are 0-based and both sequences are on the forward strand.
+pub fn maf(alignment: &Alignment, x: &str, y: &str, names: (&str, &str)) -> String {
+    let (x, y): (Vec<char>, Vec<char>) = (x.chars().collect(), y.chars().collect());
+    let columns = columns(alignment, &x, &y);
+    let line = |name: &str, start: usize, size: usize, len: usize, text: String| {
+        format!("s {} {} {} + {} {}\n", name, start, size, len, text)
+    };
+    let mut output = format!("a score={}\n", alignment.score());
+    output.push_str(&line(
+        names.0,
+        alignment.origin().x,
+        alignment.x_range().len(),
+        x.len(),
+        columns.iter().map(|column| gapped(column.0)).collect(),
+    ));
+    output.push_str(&line(
+        names.1,
+        alignment.origin().y,
+        alignment.y_range().len(),
+        y.len(),
+        columns.iter().map(|column| gapped(column.1)).collect(),
+    ));
+    output
+}
+
+// A minimal SAM record of the query `y` aligned to the reference `x`, with
+// the unaligned ends of the query soft clipped. Mapping and base qualities
+// are left unavailable, alignments without steps are reported as unmapped.
+pub fn sam(alignment: &Alignment, query: &str, reference: &str, y: &str) -> String {
+    if alignment.is_empty() {
+        return format!("{}\t4\t*\t0\t0\t*\t*\t0\t0\t{}\t*", query, y);
+    }
+    let y_len = y.chars().count();
+    let (start, end) = (alignment.origin().y, alignment.end().y);
+    let mut cigar = String::new();
+    if start > 0 {
+        cigar.push_str(&format!("{}S", start));
+    }
+    cigar.push_str(&alignment.to_cigar(Reference::X));
+    if end < y_len {
+        cigar.push_str(&format!("{}S", y_len - end));
+    }
+    let position = alignment.origin().x + 1;
+    format!(
+        "{}\t0\t{}\t{}\t255\t{}\t*\t0\t0\t{}\t*",
+        query, reference, position, cigar, y
+    )
+}
+
+// Lines of a diff, by their position in x respectively y.
+#[derive(Copy, Clone, PartialEq)]
+enum Line {
+    Context(usize, usize),
+    Removed(usize),
+    Added(usize),
+}
+
+// Unified diff hunks turning the lines (or tokens) `x` into `y`, with up to
+// `context` unchanged lines around changes. Unequal aligned lines count as
+// changed, as do lines outside of the aligned regions.
+pub fn unified_diff<T>(alignment: &Alignment, x: &[T], y: &[T], context: usize) -> String
+where
+    T: Display + PartialEq,
+{
+    let (origin, end) = (*alignment.origin(), alignment.end());
+    let mut lines: Vec<Line> = (0..origin.x).map(Line::Removed).collect();
+    lines.extend((0..origin.y).map(Line::Added));
+    let aligned = |lines: &mut Vec<Line>, i: usize, j: usize| {
+        if x[i] == y[j] {
+            lines.push(Line::Context(i, j));
+        } else {
+            lines.extend([Line::Removed(i), Line::Added(j)]);
+        }
+    };
+    for step in alignment.steps() {
+        match step {
+            Step::Align { x: i, y: j } => aligned(&mut lines, i, j),
+            Step::Transpose { x: i, y: j } => {
+                aligned(&mut lines, i, j);
+                aligned(&mut lines, i + 1, j + 1);
+            }
+            Step::Delete { x: i } => lines.push(Line::Removed(i)),
+            Step::Insert { y: j } => lines.push(Line::Added(j)),
+        }
+    }
+    lines.extend((end.x..x.len()).map(Line::Removed));
+    lines.extend((end.y..y.len()).map(Line::Added));
+
+    // Changes at most `2 * context` lines apart share a hunk.
+    let changes: Vec<usize> = (0..lines.len())
+        .filter(|&i| !matches!(lines[i], Line::Context(..)))
+        .collect();
+    let mut hunks: Vec<(usize, usize)> = vec![];
+    for &change in changes.iter() {
+        match hunks.last_mut() {
+            Some((_, last)) if change - *last <= 2 * context + 1 => *last = change,
+            _ => hunks.push((change, change)),
+        }
+    }
+
+    // The positions in x and y before each line.
+    let mut positions = Vec::with_capacity(lines.len() + 1);
+    let mut position = (0, 0);
+    for line in lines.iter() {
+        positions.push(position);
+        match line {
+            Line::Context(..) => position = (position.0 + 1, position.1 + 1),
+            Line::Removed(_) => position.0 += 1,
+            Line::Added(_) => position.1 += 1,
+        }
+    }
+    positions.push(position);
+
+    let mut output = String::new();
+    for (first, last) in hunks {
+        let start = first.saturating_sub(context);
+        let stop = (last + context + 1).min(lines.len());
+        let (from, to) = (positions[start], positions[stop]);
+        let range = |start: usize, count: usize| {
+            if count == 0 {
+                format!("{},0", start)
+            } else {
+                format!("{},{}", start + 1, count)
+            }
+        };
+        output.push_str(&format!(
+            "@@ -{} +{} @@\n",
+            range(from.0, to.0 - from.0),
+            range(from.1, to.1 - from.1)
+        ));
+        // Within a block of changes removed lines come first.
+        let mut added = vec![];
+        for line in lines[start..stop].iter() {
+            match *line {
+                Line::Context(i, _) => {
+                    for j in added.drain(..) {
+                        output.push_str(&format!("+{}\n", y[j]));
+                    }
+                    output.push_str(&format!(" {}\n", x[i]));
+                }
+                Line::Removed(i) => output.push_str(&format!("-{}\n", x[i])),
+                Line::Added(j) => added.push(j),
+            }
+        }
+        for j in added {
+            output.push_str(&format!("+{}\n", y[j]));
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pair::alignment_matrix::in_memory::AlignmentMatrix as InMemoryAlignmentMatrix;
+    use crate::pair::alignment_set::AlignmentSet;
+    use crate::pair::cursor::Cursor;
+    use crate::pair::needleman_wunsch::NeedlemanWunsch;
+    use crate::pair::smith_waterman::SmithWaterman;
+
+    #[test]
+    fn pretty_works() {
+        let x: Vec<char> = "ACGTTACG".chars().collect();
+        let y: Vec<char> = "ACTTAGG".chars().collect();
+        let strategy = NeedlemanWunsch::new(1, -1, -1, -1);
+        let set: AlignmentSet<InMemoryAlignmentMatrix> =
+            AlignmentSet::new(x.len(), y.len(), strategy, |i, j| x[i] == y[j]).unwrap();
+        let pretty = pretty(&set.global_alignment(), &x, &y, 5);
+        assert_eq!(pretty, "ACGTT\n|| ||\nAC-TT\n\nACG\n|.|\nAGG");
+        let gapped = gapped_strings(&set.global_alignment(), "ACGTTACG", "ACTTAGG", '.');
+        assert_eq!(gapped, ("ACGTTACG".to_string(), "AC.TTAGG".to_string()));
+    }
+
+    #[test]
+    fn sam_works() {
+        let x: Vec<char> = "GGGGACGTACGTGGGG".chars().collect();
+        let y: Vec<char> = "TTACGTCGTTT".chars().collect();
+        let strategy = SmithWaterman::new(2, -1, -1, -1);
+        let set: AlignmentSet<InMemoryAlignmentMatrix> =
+            AlignmentSet::new(x.len(), y.len(), strategy, |i, j| x[i] == y[j]).unwrap();
+        let y: String = y.into_iter().collect();
+        let record = sam(&set.local_alignment(), "read", "chr1", &y);
+        assert_eq!(
+            record,
+            "read\t0\tchr1\t5\t255\t2S4M1D3M2S\t*\t0\t0\tTTACGTCGTTT\t*"
+        );
+
+        let empty = Alignment::new(Cursor { x: 0, y: 0 }, vec![], 0);
+        assert!(sam(&empty, "read", "chr1", "TT").starts_with("read\t4\t*\t0\t"));
+    }
+
+    #[test]
+    fn emboss_and_maf_work() {
+        let x = "GGGGACGTACGTGGGG";
+        let y = "TTACGTCGTTT";
+        let (xs, ys): (Vec<char>, Vec<char>) = (x.chars().collect(), y.chars().collect());
+        let strategy = SmithWaterman::new(2, -1, -1, -1);
+        let set: AlignmentSet<InMemoryAlignmentMatrix> =
+            AlignmentSet::new(xs.len(), ys.len(), strategy, |i, j| xs[i] == ys[j]).unwrap();
+        let alignment = set.local_alignment();
+
+        let emboss = emboss(&alignment, x, y, ("chr1", "read"));
+        assert!(emboss.contains(concat!(
+            "# Length: 8\n",
+            "# Identity: 7/8 (87.5%)\n",
+            "# Similarity: 7/8 (87.5%)\n",
+            "# Gaps: 1/8 (12.5%)\n",
+        )));
+        assert!(emboss.ends_with(concat!(
+            "chr1               5 ACGTACGT     12\n",
+            "                     |||| |||\n",
+            "read               3 ACGT-CGT      9\n",
+        )));
+
+        let maf = maf(&alignment, x, y, ("chr1", "read"));
+        assert_eq!(
+            maf,
+            "a score=13\ns chr1 4 8 + 16 ACGTACGT\ns read 2 7 + 11 ACGT-CGT\n"
+        );
+    }
+
+    #[test]
+    fn unified_diff_works() {
+        let x = ["a", "b", "c", "d", "e", "f", "g", "h"];
+        let y = ["a", "B", "c", "d", "e", "f", "g", "h", "i"];
+        let strategy = NeedlemanWunsch::new(1, -1, -1, -1);
+        let set: AlignmentSet<InMemoryAlignmentMatrix> =
+            AlignmentSet::new(x.len(), y.len(), strategy.clone(), |i, j| x[i] == y[j]).unwrap();
+        let alignment = set.global_alignment();
+        assert_eq!(
+            unified_diff(&alignment, &x, &y, 1),
+            "@@ -1,3 +1,3 @@\n a\n-b\n+B\n c\n@@ -8,1 +8,2 @@\n h\n+i\n"
+        );
+        assert_eq!(
+            unified_diff(&alignment, &x, &y, 3),
+            "@@ -1,8 +1,9 @@\n a\n-b\n+B\n c\n d\n e\n f\n g\n h\n+i\n"
+        );
+        let set: AlignmentSet<InMemoryAlignmentMatrix> =
+            AlignmentSet::new(x.len(), x.len(), strategy, |i, j| x[i] == x[j]).unwrap();
+        assert_eq!(unified_diff(&set.global_alignment(), &x, &x, 3), "");
+    }
+}
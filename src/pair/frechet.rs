@@ -0,0 +1,119 @@
+use std::marker::PhantomData;
+
+use crate::pair::alignment_matrix::AlignmentMatrix;
+use crate::pair::cursor::Cursor;
+use crate::pair::step_mask::StepMask;
+
+// A coupling of two point sequences: both start at their first and end at
+// their last point, each step advancing one or both of them.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Coupling<C> {
+    pub distance: C,
+    pub pairs: Vec<(usize, usize)>,
+}
+
+// Discrete Fréchet distance: the coupling minimizing the largest cost of
+// its pairs, instead of their sum as in DTW. Cell (i, j) of the matrix holds
+// the step by which the pair (i, j) was reached.
+pub struct DiscreteFrechet<T> {
+    matrix: PhantomData<T>,
+}
+
+impl<T, E> DiscreteFrechet<T>
+where
+    T: AlignmentMatrix<Error = E>,
+{
+    // `cost(i, j)` is the distance of the points x[i] and y[j]. Returns
+    // `None` if either sequence is empty.
+    pub fn coupling<C, F>(x_len: usize, y_len: usize, cost: F) -> Result<Option<Coupling<C>>, E>
+    where
+        C: Copy + PartialOrd,
+        F: Fn(usize, usize) -> C,
+    {
+        if x_len == 0 || y_len == 0 {
+            return Ok(None);
+        }
+        let mut matrix = T::new(x_len, y_len)?;
+        let mut row: Vec<C> = Vec::with_capacity(x_len);
+        for y in 0..y_len {
+            let mut last_diagonal = None;
+            for x in 0..x_len {
+                let mut best: Option<(C, StepMask)> = None;
+                let mut candidate = |distance: C, step: StepMask| {
+                    if best.is_none_or(|(best_distance, _)| distance < best_distance) {
+                        best = Some((distance, step));
+                    }
+                };
+                if let Some(diagonal) = last_diagonal {
+                    candidate(diagonal, StepMask::ALIGN);
+                }
+                if x > 0 {
+                    candidate(row[x - 1], StepMask::DELETE);
+                }
+                if y > 0 {
+                    candidate(row[x], StepMask::INSERT);
+                }
+                let local = cost(x, y);
+                let (distance, step) = match best {
+                    Some((distance, step)) if local < distance => (distance, step),
+                    Some((_, step)) => (local, step),
+                    None => (local, StepMask::STOP),
+                };
+                matrix.set_at(&Cursor { x, y }, step);
+                if y > 0 {
+                    last_diagonal = Some(row[x]);
+                    row[x] = distance;
+                } else {
+                    row.push(distance);
+                }
+            }
+        }
+
+        let mut cursor = Cursor {
+            x: x_len - 1,
+            y: y_len - 1,
+        };
+        let mut pairs = vec![(cursor.x, cursor.y)];
+        loop {
+            let step = matrix.at(&cursor);
+            if step == StepMask::STOP {
+                break;
+            }
+            cursor.apply_backwards_step(step);
+            pairs.push((cursor.x, cursor.y));
+        }
+        pairs.reverse();
+        Ok(Some(Coupling {
+            distance: row[x_len - 1],
+            pairs,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pair::alignment_matrix::in_memory::AlignmentMatrix as InMemoryAlignmentMatrix;
+
+    fn coupling(x: &[(f64, f64)], y: &[(f64, f64)]) -> Option<Coupling<f64>> {
+        DiscreteFrechet::<InMemoryAlignmentMatrix>::coupling(x.len(), y.len(), |i, j| {
+            ((x[i].0 - y[j].0).powi(2) + (x[i].1 - y[j].1).powi(2)).sqrt()
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn frechet_distance_works() {
+        let x = [(0.0, 0.0), (1.0, 0.0), (2.0, 0.0), (3.0, 0.0)];
+        let y = [(0.0, 1.0), (1.5, 1.0), (3.0, 1.0)];
+        let first = coupling(&x, &y).unwrap();
+        assert!((first.distance - 1.25f64.sqrt()).abs() < 1e-9);
+        assert_eq!(first.pairs.first(), Some(&(0, 0)));
+        assert_eq!(first.pairs.last(), Some(&(3, 2)));
+
+        // A single distant point dominates the distance.
+        let y = [(0.0, 0.0), (1.0, 5.0), (3.0, 0.0)];
+        assert_eq!(coupling(&x, &y).unwrap().distance, 5.0);
+        assert_eq!(coupling(&x, &[]), None);
+    }
+}
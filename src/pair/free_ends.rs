@@ -0,0 +1,11 @@
+use bitflags::bitflags;
+
+// Ends of either sequence that may be left unaligned without penalty.
+bitflags! {
+    pub struct FreeEnds: u8 {
+        const LEADING_X  = 0b00000001;
+        const TRAILING_X = 0b00000010;
+        const LEADING_Y  = 0b00000100;
+        const TRAILING_Y = 0b00001000;
+    }
+}
@@ -0,0 +1,139 @@
+use crate::pair::strategy::{AffineGapModel, GapModel, TwoPieceGapModel};
+
+// A gap of length `n` scores `n * extend`.
+#[derive(Copy, Clone, Debug)]
+pub struct LinearGaps {
+    pub extend: isize,
+}
+
+impl GapModel for LinearGaps {
+    fn gap_score(&self, len: usize) -> isize {
+        self.extend * len as isize
+    }
+}
+
+impl AffineGapModel for LinearGaps {
+    fn open_score(&self) -> isize {
+        0
+    }
+
+    fn extend_score(&self) -> isize {
+        self.extend
+    }
+}
+
+impl TwoPieceGapModel for LinearGaps {
+    fn pieces(&self) -> [AffineGaps; 2] {
+        let piece = AffineGaps {
+            open: 0,
+            extend: self.extend,
+        };
+        [piece, piece]
+    }
+}
+
+// A gap of length `n` scores `open + n * extend`.
+#[derive(Copy, Clone, Debug)]
+pub struct AffineGaps {
+    pub open: isize,
+    pub extend: isize,
+}
+
+impl GapModel for AffineGaps {
+    fn gap_score(&self, len: usize) -> isize {
+        self.open + self.extend * len as isize
+    }
+}
+
+impl AffineGapModel for AffineGaps {
+    fn open_score(&self) -> isize {
+        self.open
+    }
+
+    fn extend_score(&self) -> isize {
+        self.extend
+    }
+}
+
+impl TwoPieceGapModel for AffineGaps {
+    fn pieces(&self) -> [AffineGaps; 2] {
+        [*self, *self]
+    }
+}
+
+// The better of two affine models, usually one for short and one for long gaps.
+#[derive(Copy, Clone, Debug)]
+pub struct TwoPieceGaps {
+    pub short: AffineGaps,
+    pub long: AffineGaps,
+}
+
+impl GapModel for TwoPieceGaps {
+    fn gap_score(&self, len: usize) -> isize {
+        std::cmp::max(self.short.gap_score(len), self.long.gap_score(len))
+    }
+}
+
+impl TwoPieceGapModel for TwoPieceGaps {
+    fn pieces(&self) -> [AffineGaps; 2] {
+        [self.short, self.long]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pair::affine_alignment_set::AffineAlignmentSet;
+    use crate::pair::alignment_matrix::in_memory::AlignmentMatrix as InMemoryAlignmentMatrix;
+    use crate::pair::alignment_set::AlignmentSet;
+    use crate::pair::convex::Convex;
+    use crate::pair::gotoh::Gotoh;
+    use crate::pair::needleman_wunsch::NeedlemanWunsch;
+    use crate::pair::two_piece::TwoPiece;
+    use crate::pair::two_piece_alignment_set::TwoPieceAlignmentSet;
+    use crate::pair::waterman_smith_beyer::WatermanSmithBeyer;
+
+    fn score<G: GapModel>(x: &[char], y: &[char], gaps: G) -> isize {
+        WatermanSmithBeyer::new(Convex::new(1, -1, gaps))
+            .global_alignment(x.len(), y.len(), |i, j| x[i] == y[j])
+            .score()
+    }
+
+    #[test]
+    fn gap_models_compose() {
+        let x: Vec<char> = "ACGTTTTTTTTTTACGT".chars().collect();
+        let y: Vec<char> = "ACGTACGT".chars().collect();
+        let f = |i: usize, j: usize| x[i] == y[j];
+
+        let affine = AffineGaps {
+            open: -4,
+            extend: -1,
+        };
+        let set: AffineAlignmentSet<InMemoryAlignmentMatrix> =
+            AffineAlignmentSet::new(x.len(), y.len(), Gotoh::from_gaps(1, -1, affine), f).unwrap();
+        assert_eq!(score(&x, &y, affine), set.global_score());
+
+        let linear = LinearGaps { extend: -2 };
+        let set: AffineAlignmentSet<InMemoryAlignmentMatrix> =
+            AffineAlignmentSet::new(x.len(), y.len(), Gotoh::from_gaps(1, -1, linear), f).unwrap();
+        let plain = NeedlemanWunsch::new(1, -1, -2, -2);
+        let plain =
+            AlignmentSet::<InMemoryAlignmentMatrix>::new(x.len(), y.len(), plain, f).unwrap();
+        assert_eq!(score(&x, &y, linear), set.global_score());
+        assert_eq!(plain.global_score(), set.global_score());
+
+        let two_piece = TwoPieceGaps {
+            short: affine,
+            long: AffineGaps {
+                open: -8,
+                extend: 0,
+            },
+        };
+        assert_eq!(score(&x, &y, two_piece), 8 - 8);
+        let set: TwoPieceAlignmentSet<InMemoryAlignmentMatrix> =
+            TwoPieceAlignmentSet::new(x.len(), y.len(), TwoPiece::from_gaps(1, -1, two_piece), f)
+                .unwrap();
+        assert_eq!(score(&x, &y, two_piece), set.global_score());
+        assert_eq!(LinearGaps { extend: -2 }.gap_score(3), -6);
+    }
+}
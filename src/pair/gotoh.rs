@@ -0,0 +1,61 @@
+use crate::pair::gap_models::AffineGaps;
+use crate::pair::strategy::{AffineGapModel, AffineStrategy};
+
+#[derive(Clone, Debug)]
+pub struct Gotoh<G = AffineGaps> {
+    pub equal: isize,
+    pub align: isize,
+    pub gaps: G,
+    // Gaps of insertions, if they differ from deletions.
+    pub insert: Option<G>,
+}
+
+impl Gotoh {
+    pub fn new(equal: isize, align: isize, open: isize, extend: isize) -> Gotoh {
+        Gotoh::from_gaps(equal, align, AffineGaps { open, extend })
+    }
+}
+
+impl<G> Gotoh<G>
+where
+    G: AffineGapModel,
+{
+    // Scores gaps by the model `gaps`, e.g. `LinearGaps` to align like
+    // `NeedlemanWunsch`.
+    pub fn from_gaps(equal: isize, align: isize, gaps: G) -> Gotoh<G> {
+        Gotoh {
+            equal,
+            align,
+            gaps,
+            insert: None,
+        }
+    }
+
+    pub fn with_insert_gaps(mut self, insert: G) -> Gotoh<G> {
+        self.insert = Some(insert);
+        self
+    }
+}
+
+impl<G> AffineStrategy for Gotoh<G>
+where
+    G: AffineGapModel,
+{
+    type Gaps = G;
+
+    fn match_score(&self) -> isize {
+        self.equal
+    }
+
+    fn mismatch_score(&self) -> isize {
+        self.align
+    }
+
+    fn gaps(&self) -> &G {
+        &self.gaps
+    }
+
+    fn insert_gaps(&self) -> &G {
+        self.insert.as_ref().unwrap_or(&self.gaps)
+    }
+}
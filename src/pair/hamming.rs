@@ -0,0 +1,97 @@
+use crate::pair::alignment_matrix::AlignmentMatrix;
+use crate::pair::alignment_set::{AlignmentSet, ScoreError};
+use crate::pair::step_mask::StepMask;
+use crate::pair::strategy::Strategy;
+
+#[derive(Debug)]
+pub enum HammingError<E> {
+    LengthMismatch { x_len: usize, y_len: usize },
+    Score(ScoreError<E>),
+}
+
+// Counts positional mismatches of equal-length sequences. The band of width
+// zero restricts the alignment to align steps on the main diagonal.
+#[derive(Clone, Debug, Default)]
+pub struct Hamming;
+
+impl Hamming {
+    pub fn new() -> Hamming {
+        Hamming
+    }
+
+    // The global score is the negated Hamming distance.
+    pub fn alignment_set<M, E, F>(
+        &self,
+        x_len: usize,
+        y_len: usize,
+        f: F,
+    ) -> Result<AlignmentSet<M>, HammingError<E>>
+    where
+        M: AlignmentMatrix<Error = E>,
+        F: Fn(usize, usize) -> bool,
+    {
+        if x_len != y_len {
+            return Err(HammingError::LengthMismatch { x_len, y_len });
+        }
+        AlignmentSet::new(x_len, y_len, self.clone(), f).map_err(HammingError::Score)
+    }
+}
+
+impl Strategy for Hamming {
+    fn match_score(&self) -> isize {
+        0
+    }
+
+    fn mismatch_score(&self) -> isize {
+        -1
+    }
+
+    fn insert_score(&self) -> isize {
+        -1
+    }
+
+    fn delete_score(&self) -> isize {
+        -1
+    }
+
+    fn total_score(&self, score: isize) -> isize {
+        score
+    }
+
+    fn bandwidth(&self) -> Option<usize> {
+        Some(0)
+    }
+
+    fn step_mask(&self, align: isize, insert: isize, delete: isize) -> StepMask {
+        StepMask::from_scores(align, delete, insert)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pair::alignment_matrix::in_memory::AlignmentMatrix as InMemoryAlignmentMatrix;
+
+    #[test]
+    fn hamming_works() {
+        let x: Vec<char> = "karolin".chars().collect();
+        let y: Vec<char> = "kathrin".chars().collect();
+        let set: AlignmentSet<InMemoryAlignmentMatrix> = Hamming::new()
+            .alignment_set(x.len(), y.len(), |i, j| x[i] == y[j])
+            .unwrap();
+        assert_eq!(set.global_score(), -3);
+        let alignment = set.global_alignment();
+        assert_eq!(alignment.len(), 7);
+        assert!(alignment.steps().all(|step| step.mask() == StepMask::ALIGN));
+    }
+
+    #[test]
+    fn different_lengths_are_rejected() {
+        let result: Result<AlignmentSet<InMemoryAlignmentMatrix>, _> =
+            Hamming::new().alignment_set(3, 4, |_, _| true);
+        assert!(matches!(
+            result,
+            Err(HammingError::LengthMismatch { x_len: 3, y_len: 4 })
+        ));
+    }
+}
@@ -0,0 +1,245 @@
+use std::cmp;
+
+use crate::pair::alignment::Alignment;
+use crate::pair::cursor::Cursor;
+use crate::pair::step_mask::StepMask;
+use crate::pair::strategy::Strategy;
+
+// Scores are kept for a single row only, which is always laid out
+// along the shorter of the two sequences.
+struct Scoring<F> {
+    substitution: F,
+    insert: isize,
+    delete: isize,
+}
+
+impl<F> Scoring<F>
+where
+    F: Fn(usize, usize) -> isize,
+{
+    fn forward_row(&self, x: (usize, usize), y: (usize, usize)) -> Vec<isize> {
+        let (y_start, y_end) = y;
+        let mut row: Vec<isize> = (0..=(y_end - y_start))
+            .map(|j| self.insert * (j as isize))
+            .collect();
+        for i in x.0..x.1 {
+            let mut last_diagonal = row[0];
+            row[0] += self.delete;
+            for j in y_start..y_end {
+                let k = j - y_start + 1;
+                let align = last_diagonal + (self.substitution)(i, j);
+                let delete = row[k] + self.delete;
+                let insert = row[k - 1] + self.insert;
+                last_diagonal = row[k];
+                row[k] = cmp::max(cmp::max(align, delete), insert);
+            }
+        }
+        row
+    }
+
+    fn backward_row(&self, x: (usize, usize), y: (usize, usize)) -> Vec<isize> {
+        let (y_start, y_end) = y;
+        let len = y_end - y_start;
+        let mut row: Vec<isize> = (0..=len)
+            .map(|j| self.insert * ((len - j) as isize))
+            .collect();
+        for i in (x.0..x.1).rev() {
+            let mut last_diagonal = row[len];
+            row[len] += self.delete;
+            for j in (y_start..y_end).rev() {
+                let k = j - y_start;
+                let align = last_diagonal + (self.substitution)(i, j);
+                let delete = row[k] + self.delete;
+                let insert = row[k + 1] + self.insert;
+                last_diagonal = row[k];
+                row[k] = cmp::max(cmp::max(align, delete), insert);
+            }
+        }
+        row
+    }
+
+    fn align_single(&self, i: usize, y: (usize, usize), steps: &mut Vec<StepMask>) {
+        let (y_start, y_end) = y;
+        let gaps = self.insert * ((y_end - y_start - 1) as isize);
+        let mut best: Option<(usize, isize)> = None;
+        for j in y_start..y_end {
+            let score = (self.substitution)(i, j) + gaps;
+            if best.is_none_or(|(_, best_score)| score > best_score) {
+                best = Some((j, score));
+            }
+        }
+        match best {
+            Some((j, score)) if score >= gaps + self.insert + self.delete => {
+                steps.extend((y_start..j).map(|_| StepMask::INSERT));
+                steps.push(StepMask::ALIGN);
+                steps.extend(((j + 1)..y_end).map(|_| StepMask::INSERT));
+            }
+            _ => {
+                steps.push(StepMask::DELETE);
+                steps.extend((y_start..y_end).map(|_| StepMask::INSERT));
+            }
+        }
+    }
+
+    fn align(&self, x: (usize, usize), y: (usize, usize), steps: &mut Vec<StepMask>) {
+        let (x_start, x_end) = x;
+        let (y_start, y_end) = y;
+        if x_start == x_end {
+            steps.extend((y_start..y_end).map(|_| StepMask::INSERT));
+        } else if y_start == y_end {
+            steps.extend((x_start..x_end).map(|_| StepMask::DELETE));
+        } else if x_end - x_start == 1 {
+            self.align_single(x_start, y, steps);
+        } else {
+            let x_mid = (x_start + x_end) / 2;
+            let forward = self.forward_row((x_start, x_mid), y);
+            let backward = self.backward_row((x_mid, x_end), y);
+            let (split, _) = forward.iter().zip(backward.iter()).enumerate().fold(
+                (0, isize::MIN),
+                |best, (j, (f, b))| {
+                    if f + b > best.1 {
+                        (j, f + b)
+                    } else {
+                        best
+                    }
+                },
+            );
+            let y_mid = y_start + split;
+            self.align((x_start, x_mid), (y_start, y_mid), steps);
+            self.align((x_mid, x_end), (y_mid, y_end), steps);
+        }
+    }
+
+    fn score(&self, x_len: usize, y_len: usize) -> isize {
+        self.forward_row((0, x_len), (0, y_len))[y_len]
+    }
+}
+
+// Global alignment in linear space; only the linear scores of the strategy
+// are used, its `total_score` is not applied. Of co-optimal alignments the
+// one through the first best split of each half is returned, there is no
+// `Preference` to choose another, as splits are not tracebacks.
+#[derive(Clone, Debug)]
+pub struct Hirschberg<S> {
+    strategy: S,
+}
+
+impl<S> Hirschberg<S>
+where
+    S: Strategy,
+{
+    pub fn new(strategy: S) -> Hirschberg<S> {
+        Hirschberg { strategy }
+    }
+
+    pub fn global_alignment<F>(&self, x_len: usize, y_len: usize, f: F) -> Alignment
+    where
+        F: Fn(usize, usize) -> bool,
+    {
+        let strategy = &self.strategy;
+        let substitution = |x: usize, y: usize| {
+            if f(x, y) {
+                strategy.match_score()
+            } else {
+                strategy.mismatch_score()
+            }
+        };
+        let mut steps = vec![];
+        let score = if y_len <= x_len {
+            let scoring = Scoring {
+                substitution,
+                insert: strategy.insert_score(),
+                delete: strategy.delete_score(),
+            };
+            scoring.align((0, x_len), (0, y_len), &mut steps);
+            scoring.score(x_len, y_len)
+        } else {
+            let scoring = Scoring {
+                substitution: |y: usize, x: usize| substitution(x, y),
+                insert: strategy.delete_score(),
+                delete: strategy.insert_score(),
+            };
+            scoring.align((0, y_len), (0, x_len), &mut steps);
+            for step in steps.iter_mut() {
+                *step = match *step {
+                    StepMask::DELETE => StepMask::INSERT,
+                    StepMask::INSERT => StepMask::DELETE,
+                    mask => mask,
+                };
+            }
+            scoring.score(y_len, x_len)
+        };
+        Alignment::new(Cursor { x: 0, y: 0 }, steps, score)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pair::alignment_matrix::in_memory::AlignmentMatrix as InMemoryAlignmentMatrix;
+    use crate::pair::alignment_set::AlignmentSet;
+    use crate::pair::needleman_wunsch::NeedlemanWunsch;
+    use crate::pair::step::Step;
+
+    fn score_of(
+        x: &[char],
+        y: &[char],
+        strategy: &NeedlemanWunsch,
+        alignment: &Alignment,
+    ) -> isize {
+        alignment
+            .steps()
+            .map(|step| match step {
+                Step::Align { x: i, y: j } if x[i] == y[j] => strategy.equal,
+                Step::Align { .. } => strategy.align,
+                Step::Delete { .. } => strategy.delete,
+                Step::Insert { .. } => strategy.insert,
+                Step::Transpose { .. } => unreachable!(),
+            })
+            .sum()
+    }
+
+    fn check(str_x: &str, str_y: &str) {
+        let x: Vec<char> = str_x.chars().collect();
+        let y: Vec<char> = str_y.chars().collect();
+        let strategy = NeedlemanWunsch::new(2, -1, -1, -1);
+        let hirschberg = Hirschberg::new(strategy.clone());
+        let alignment = hirschberg.global_alignment(x.len(), y.len(), |i, j| x[i] == y[j]);
+        let set: AlignmentSet<InMemoryAlignmentMatrix> =
+            AlignmentSet::new(x.len(), y.len(), strategy.clone(), |i, j| x[i] == y[j]).unwrap();
+        assert_eq!(alignment.score(), set.global_score());
+        assert_eq!(score_of(&x, &y, &strategy, &alignment), alignment.score());
+        let mut cursor = *alignment.origin();
+        for step in alignment.steps() {
+            cursor.apply_forwards_step(step.mask());
+        }
+        assert_eq!(
+            cursor,
+            Cursor {
+                x: x.len(),
+                y: y.len()
+            }
+        );
+    }
+
+    #[test]
+    fn global_alignment_works() {
+        check(
+            "The quick brown fox jumps over the lazy dog.",
+            "The brown dog jumps over the very lazy snail.",
+        );
+    }
+
+    #[test]
+    fn transposed_works() {
+        check("ACGT", "AACCGGTT");
+        check("AACCGGTT", "ACGT");
+    }
+
+    #[test]
+    fn empty_works() {
+        check("", "ABC");
+        check("ABC", "");
+        check("", "");
+    }
+}
@@ -0,0 +1,89 @@
+// Scores nucleotides given as IUPAC codes, case-insensitively. Two codes
+// whose base sets overlap without both being the same single base, e.g. `R`
+// (A or G) and `A`, score `partial`.
+#[derive(Clone, Debug)]
+pub struct Iupac {
+    pub equal: isize,
+    pub partial: isize,
+    pub align: isize,
+}
+
+impl Iupac {
+    pub fn new(equal: isize, partial: isize, align: isize) -> Iupac {
+        Iupac {
+            equal,
+            partial,
+            align,
+        }
+    }
+
+    // The bases of a code as a bit set of A, C, G and T, empty for unknown codes.
+    pub fn bases(code: u8) -> u8 {
+        const A: u8 = 1;
+        const C: u8 = 2;
+        const G: u8 = 4;
+        const T: u8 = 8;
+        match code.to_ascii_uppercase() {
+            b'A' => A,
+            b'C' => C,
+            b'G' => G,
+            b'T' | b'U' => T,
+            b'R' => A | G,
+            b'Y' => C | T,
+            b'S' => C | G,
+            b'W' => A | T,
+            b'K' => G | T,
+            b'M' => A | C,
+            b'B' => C | G | T,
+            b'D' => A | G | T,
+            b'H' => A | C | T,
+            b'V' => A | C | G,
+            b'N' => A | C | G | T,
+            _ => 0,
+        }
+    }
+
+    pub fn score(&self, a: u8, b: u8) -> isize {
+        let (a, b) = (Self::bases(a), Self::bases(b));
+        if a == b && a.count_ones() == 1 {
+            self.equal
+        } else if a & b != 0 {
+            self.partial
+        } else {
+            self.align
+        }
+    }
+
+    // Scores the IUPAC code x[i] against y[j], so that e.g. `N` in a read
+    // scores `partial` against any base of the reference.
+    pub fn scores<'a>(&'a self, x: &'a [u8], y: &'a [u8]) -> impl Fn(usize, usize) -> isize + 'a {
+        move |i, j| self.score(x[i], y[j])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pair::alignment_matrix::in_memory::AlignmentMatrix as InMemoryAlignmentMatrix;
+    use crate::pair::alignment_set::AlignmentSet;
+    use crate::pair::needleman_wunsch::NeedlemanWunsch;
+
+    #[test]
+    fn ambiguity_codes_match_partially() {
+        let iupac = Iupac::new(2, 1, -1);
+        assert_eq!(iupac.score(b'A', b'a'), 2);
+        assert_eq!(iupac.score(b'R', b'G'), 1);
+        assert_eq!(iupac.score(b'N', b'N'), 1);
+        assert_eq!(iupac.score(b'R', b'Y'), -1);
+        assert_eq!(iupac.score(b'U', b'T'), 2);
+
+        let x = b"ACGTRYACGT";
+        let y = b"ACGNGTACGT";
+        let strategy = NeedlemanWunsch::new(0, 0, -2, -2);
+        let set: AlignmentSet<InMemoryAlignmentMatrix> =
+            AlignmentSet::builder(x.len(), y.len(), strategy)
+                .build_with_scores(iupac.scores(x, y))
+                .unwrap();
+        assert_eq!(set.global_score(), 17);
+    }
+}
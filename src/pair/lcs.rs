@@ -0,0 +1,87 @@
+use crate::pair::alignment::Alignment;
+use crate::pair::alignment_set::MIN_SCORE;
+use crate::pair::step::Step;
+use crate::pair::step_mask::StepMask;
+use crate::pair::strategy::Strategy;
+
+// Positions of a common subsequence and of the elements only found in x or y.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CommonSubsequence {
+    pub common: Vec<(usize, usize)>,
+    pub x_only: Vec<usize>,
+    pub y_only: Vec<usize>,
+}
+
+// Longest common subsequence: matches score one, gaps are free and
+// mismatches are never aligned. The global score is the length of the LCS.
+#[derive(Clone, Debug, Default)]
+pub struct Lcs;
+
+impl Lcs {
+    pub fn new() -> Lcs {
+        Lcs
+    }
+
+    pub fn common_subsequence(alignment: &Alignment) -> CommonSubsequence {
+        let mut result = CommonSubsequence::default();
+        for step in alignment.steps() {
+            match step {
+                Step::Align { x, y } => result.common.push((x, y)),
+                Step::Delete { x } => result.x_only.push(x),
+                Step::Insert { y } => result.y_only.push(y),
+                Step::Transpose { x, y } => {
+                    result.x_only.extend(x..x + 2);
+                    result.y_only.extend(y..y + 2);
+                }
+            }
+        }
+        result
+    }
+}
+
+impl Strategy for Lcs {
+    fn match_score(&self) -> isize {
+        1
+    }
+
+    fn mismatch_score(&self) -> isize {
+        MIN_SCORE
+    }
+
+    fn insert_score(&self) -> isize {
+        0
+    }
+
+    fn delete_score(&self) -> isize {
+        0
+    }
+
+    fn total_score(&self, score: isize) -> isize {
+        score
+    }
+
+    fn step_mask(&self, align: isize, insert: isize, delete: isize) -> StepMask {
+        StepMask::from_scores(align, delete, insert)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pair::alignment_matrix::in_memory::AlignmentMatrix as InMemoryAlignmentMatrix;
+    use crate::pair::alignment_set::AlignmentSet;
+
+    #[test]
+    fn lcs_works() {
+        let x: Vec<char> = "ABCBDAB".chars().collect();
+        let y: Vec<char> = "BDCABA".chars().collect();
+        let set: AlignmentSet<InMemoryAlignmentMatrix> =
+            AlignmentSet::new(x.len(), y.len(), Lcs::new(), |i, j| x[i] == y[j]).unwrap();
+        assert_eq!(set.global_score(), 4);
+        let lcs = Lcs::common_subsequence(&set.global_alignment());
+        assert_eq!(lcs.common.len(), 4);
+        assert!(lcs.common.iter().all(|&(i, j)| x[i] == y[j]));
+        assert_eq!(lcs.x_only.len(), 3);
+        assert_eq!(lcs.y_only.len(), 2);
+    }
+}
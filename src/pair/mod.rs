@@ -1,36 +1,118 @@
+pub mod boundary;
 pub mod cursor;
+pub mod free_ends;
 pub mod run;
 pub mod step;
 pub mod step_mask;
 pub mod strategy;
 
+pub mod anchored;
+pub mod case_folding;
+pub mod convex;
+pub mod damerau_levenshtein;
+pub mod frechet;
+pub mod gap_models;
+pub mod gotoh;
+pub mod hamming;
+pub mod hirschberg;
+pub mod iupac;
+pub mod lcs;
+pub mod myers;
 pub mod needleman_wunsch;
+pub mod positional_gaps;
+pub mod profile;
+pub mod pssm;
+pub mod quality_aware;
+pub mod score_overrides;
+pub(crate) mod score_table;
+pub mod seed_extend;
 pub mod smith_waterman;
+pub mod soft_mask;
+pub mod two_piece;
+pub mod waterman_eggert;
+pub mod waterman_smith_beyer;
+pub mod wavefront;
+pub mod weights;
+pub mod wildcards;
+pub mod x_drop;
 
 pub mod alignment;
+pub mod edit;
+pub mod format;
+pub mod pairs;
 pub mod runs;
 pub mod steps;
 
+pub mod affine_alignment_set;
 pub mod alignment_matrix;
 pub mod alignment_set;
 pub mod alignments;
+pub mod checkpointed_alignment_set;
+pub(crate) mod circular;
+pub(crate) mod suboptimal;
+pub mod two_piece_alignment_set;
 
+pub use self::boundary::Boundary;
 pub use self::cursor::Cursor;
+pub use self::free_ends::FreeEnds;
 pub use self::run::Run;
 pub use self::step::Step;
 pub use self::step_mask::StepMask;
-pub use self::strategy::Strategy;
+pub use self::strategy::{
+    AffineGapModel, AffineStrategy, GapModel, GapStrategy, Strategy, TwoPieceGapModel,
+    TwoPieceStrategy,
+};
 
+#[cfg(feature = "memory-mapped")]
+pub use self::alignment_matrix::memory_mapped::AlignmentMatrix as MemoryMappedAlignmentMatrix;
 pub use self::alignment_matrix::{
+    anti_diagonal::AlignmentMatrix as AntiDiagonalAlignmentMatrix,
+    banded::AlignmentMatrix as BandedAlignmentMatrix,
+    buffered::{AlignmentMatrix as BufferedAlignmentMatrix, Buffer},
     in_memory::AlignmentMatrix as InMemoryAlignmentMatrix,
-    memory_mapped::AlignmentMatrix as MemoryMappedAlignmentMatrix, AlignmentMatrix,
+    packed::AlignmentMatrix as PackedAlignmentMatrix,
+    run_length::AlignmentMatrix as RunLengthAlignmentMatrix,
+    score_only::AlignmentMatrix as ScoreOnlyAlignmentMatrix,
+    sparse::AlignmentMatrix as SparseAlignmentMatrix,
+    AlignmentMatrix,
 };
+pub use self::anchored::{AnchorError, Anchored};
+pub use self::case_folding::CaseFolding;
+pub use self::convex::Convex;
+pub use self::damerau_levenshtein::DamerauLevenshtein;
+pub use self::frechet::{Coupling, DiscreteFrechet};
+pub use self::gap_models::{AffineGaps, LinearGaps, TwoPieceGaps};
+pub use self::gotoh::Gotoh;
+pub use self::hamming::{Hamming, HammingError};
+pub use self::hirschberg::Hirschberg;
+pub use self::iupac::Iupac;
+pub use self::lcs::{CommonSubsequence, Lcs};
+pub use self::myers::Myers;
 pub use self::needleman_wunsch::NeedlemanWunsch;
+pub use self::positional_gaps::PositionalGaps;
+pub use self::profile::{Profile, ProfileError};
+pub use self::pssm::{Pssm, PssmError};
+pub use self::quality_aware::{QualityAware, QualityError};
+pub use self::score_overrides::ScoreOverrides;
+pub use self::seed_extend::{KmerIndex, Seed, SeedExtend, SeedExtendError};
 pub use self::smith_waterman::SmithWaterman;
+pub use self::soft_mask::SoftMask;
+pub use self::two_piece::TwoPiece;
+pub use self::waterman_eggert::WatermanEggert;
+pub use self::waterman_smith_beyer::WatermanSmithBeyer;
+pub use self::wavefront::{Wavefront, WavefrontError};
+pub use self::weights::Weights;
+pub use self::wildcards::Wildcards;
+pub use self::x_drop::XDrop;
 
-pub use self::alignment::Alignment;
+pub use self::alignment::{Alignment, Reference};
+pub use self::edit::Edit;
+pub use self::pairs::Pairs;
 pub use self::runs::Runs;
 pub use self::steps::Steps;
 
-pub use self::alignment_set::AlignmentSet;
-pub use self::alignments::Alignments;
+pub use self::affine_alignment_set::AffineAlignmentSet;
+pub use self::alignment_set::{AlignmentSet, AlignmentSetBuilder, ScoreError};
+pub use self::alignments::{Alignments, Preference};
+pub use self::checkpointed_alignment_set::CheckpointedAlignmentSet;
+pub use self::two_piece_alignment_set::TwoPieceAlignmentSet;
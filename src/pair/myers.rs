@@ -0,0 +1,159 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::pair::alignment_matrix::AlignmentMatrix;
+use crate::pair::alignment_set::{AlignmentSet, ScoreError};
+use crate::pair::needleman_wunsch::NeedlemanWunsch;
+
+const WORD_SIZE: usize = 64;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Hit {
+    pub distance: usize,
+    pub end: usize,
+}
+
+// Bit-parallel edit distance for patterns of up to 64 elements.
+#[derive(Clone, Debug)]
+pub struct Myers<'a, T: 'a> {
+    pattern: &'a [T],
+    peq: HashMap<&'a T, u64>,
+}
+
+impl<'a, T> Myers<'a, T>
+where
+    T: Eq + Hash,
+{
+    pub fn new(pattern: &'a [T]) -> Option<Myers<'a, T>> {
+        if pattern.len() > WORD_SIZE {
+            return None;
+        }
+        let mut peq = HashMap::new();
+        for (i, symbol) in pattern.iter().enumerate() {
+            *peq.entry(symbol).or_insert(0) |= 1 << i;
+        }
+        Some(Myers { pattern, peq })
+    }
+
+    // Runs the column recurrence over `text`, calling `f` with the
+    // score of the last row after each column.
+    fn scan<F>(&self, text: &[T], global: bool, mut f: F)
+    where
+        F: FnMut(usize, usize),
+    {
+        let len = self.pattern.len();
+        let high = 1 << (len - 1);
+        let mut pv: u64 = !0;
+        let mut mv: u64 = 0;
+        let mut score = len;
+        for (j, symbol) in text.iter().enumerate() {
+            let eq = self.peq.get(symbol).cloned().unwrap_or(0);
+            let xv = eq | mv;
+            let xh = ((eq & pv).wrapping_add(pv) ^ pv) | eq;
+            let mut ph = mv | !(xh | pv);
+            let mut mh = pv & xh;
+            if ph & high != 0 {
+                score += 1;
+            } else if mh & high != 0 {
+                score -= 1;
+            }
+            ph = (ph << 1) | (global as u64);
+            mh <<= 1;
+            pv = mh | !(xv | ph);
+            mv = ph & xv;
+            f(j, score);
+        }
+    }
+
+    pub fn distance(&self, text: &[T]) -> usize {
+        if self.pattern.is_empty() {
+            return text.len();
+        }
+        let mut distance = self.pattern.len();
+        self.scan(text, true, |_, score| distance = score);
+        distance
+    }
+
+    // Finds the end of the best occurrence of the pattern anywhere in `text`.
+    pub fn search(&self, text: &[T]) -> Hit {
+        let mut hit = Hit {
+            distance: self.pattern.len(),
+            end: 0,
+        };
+        if self.pattern.is_empty() {
+            return hit;
+        }
+        self.scan(text, false, |j, score| {
+            if score < hit.distance {
+                hit = Hit {
+                    distance: score,
+                    end: j + 1,
+                };
+            }
+        });
+        hit
+    }
+
+    // Global alignment of `text` (x) against the pattern (y), restricted
+    // to the band that an optimal path of the computed distance can reach.
+    pub fn alignment_set<M, E>(&self, text: &[T]) -> Result<AlignmentSet<M>, ScoreError<E>>
+    where
+        M: AlignmentMatrix<Error = E>,
+    {
+        let distance = self.distance(text);
+        let strategy = NeedlemanWunsch::new(0, -1, -1, -1).with_bandwidth(distance);
+        let pattern = self.pattern;
+        AlignmentSet::new(text.len(), pattern.len(), strategy, |x, y| {
+            text[x] == pattern[y]
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pair::alignment_matrix::in_memory::AlignmentMatrix as InMemoryAlignmentMatrix;
+
+    fn chars(string: &str) -> Vec<char> {
+        string.chars().collect()
+    }
+
+    #[test]
+    fn distance_works() {
+        let pattern = chars("kitten");
+        let myers = Myers::new(&pattern).unwrap();
+        assert_eq!(myers.distance(&chars("sitting")), 3);
+        assert_eq!(myers.distance(&chars("kitten")), 0);
+        assert_eq!(myers.distance(&chars("")), 6);
+    }
+
+    #[test]
+    fn search_works() {
+        let pattern = chars("ACGT");
+        let myers = Myers::new(&pattern).unwrap();
+        let hit = myers.search(&chars("XXXACCTXXX"));
+        assert_eq!(
+            hit,
+            Hit {
+                distance: 1,
+                end: 7
+            }
+        );
+    }
+
+    #[test]
+    fn long_patterns_are_rejected() {
+        let pattern = vec![0; 65];
+        assert!(Myers::new(&pattern).is_none());
+    }
+
+    #[test]
+    fn alignment_set_works() {
+        let pattern = chars("The quick brown fox");
+        let text = chars("The quack brown fix jumps");
+        let myers = Myers::new(&pattern).unwrap();
+        let set: AlignmentSet<InMemoryAlignmentMatrix> = myers.alignment_set(&text).unwrap();
+        assert_eq!(set.global_score(), -(myers.distance(&text) as isize));
+        assert_eq!(set.global_score(), -8);
+    }
+}
@@ -1,3 +1,5 @@
+use crate::pair::boundary::Boundary;
+use crate::pair::free_ends::FreeEnds;
 use crate::pair::step_mask::StepMask;
 use crate::pair::strategy::Strategy;
 
@@ -7,6 +9,13 @@ pub struct NeedlemanWunsch {
     pub align: isize,
     pub insert: isize,
     pub delete: isize,
+    pub bandwidth: Option<usize>,
+    pub free_ends: FreeEnds,
+    pub circular: bool,
+    // Insert and delete scores of terminal gaps.
+    pub terminal_gaps: Option<(isize, isize)>,
+    // Initialization of the first row and column, overriding `free_ends`.
+    pub boundaries: Option<(Boundary, Boundary)>,
 }
 
 impl NeedlemanWunsch {
@@ -16,8 +25,47 @@ impl NeedlemanWunsch {
             align,
             insert,
             delete,
+            bandwidth: None,
+            free_ends: FreeEnds::empty(),
+            circular: false,
+            terminal_gaps: None,
+            boundaries: None,
         }
     }
+
+    pub fn with_bandwidth(mut self, bandwidth: usize) -> NeedlemanWunsch {
+        self.bandwidth = Some(bandwidth);
+        self
+    }
+
+    // Gaps at the given ends of the sequences are not penalized.
+    pub fn with_free_ends(mut self, free_ends: FreeEnds) -> NeedlemanWunsch {
+        self.free_ends = free_ends;
+        self
+    }
+
+    // x gets aligned at its best rotation, in O(log(x_len)) times the time
+    // of a fill unless terminal gaps, boundaries or a band make scores
+    // depend on the rotation, which costs a fill per rotation.
+    pub fn with_circular(mut self) -> NeedlemanWunsch {
+        self.circular = true;
+        self
+    }
+
+    // Gaps at the ends of the sequences score `insert` and `delete` instead.
+    pub fn with_terminal_gaps(mut self, insert: isize, delete: isize) -> NeedlemanWunsch {
+        self.terminal_gaps = Some((insert, delete));
+        self
+    }
+
+    pub fn with_boundaries(
+        mut self,
+        first_row: Boundary,
+        first_column: Boundary,
+    ) -> NeedlemanWunsch {
+        self.boundaries = Some((first_row, first_column));
+        self
+    }
 }
 
 impl Strategy for NeedlemanWunsch {
@@ -37,11 +85,45 @@ impl Strategy for NeedlemanWunsch {
         self.delete
     }
 
+    fn terminal_delete_score_at(&self, _x: usize, _y: usize) -> isize {
+        self.terminal_gaps.map_or(self.delete, |(_, delete)| delete)
+    }
+
+    fn terminal_insert_score_at(&self, _x: usize, _y: usize) -> isize {
+        self.terminal_gaps.map_or(self.insert, |(insert, _)| insert)
+    }
+
     fn total_score(&self, score: isize) -> isize {
         score
     }
 
+    fn bandwidth(&self) -> Option<usize> {
+        self.bandwidth
+    }
+
+    fn free_ends(&self) -> FreeEnds {
+        self.free_ends
+    }
+
+    fn first_row(&self) -> Boundary {
+        match self.boundaries {
+            Some((first_row, _)) => first_row,
+            None => Boundary::of(self.free_ends, FreeEnds::LEADING_X),
+        }
+    }
+
+    fn first_column(&self) -> Boundary {
+        match self.boundaries {
+            Some((_, first_column)) => first_column,
+            None => Boundary::of(self.free_ends, FreeEnds::LEADING_Y),
+        }
+    }
+
+    fn circular(&self) -> bool {
+        self.circular
+    }
+
     fn step_mask(&self, align: isize, insert: isize, delete: isize) -> StepMask {
-        StepMask::from_scores(align, insert, delete)
+        StepMask::from_scores(align, delete, insert)
     }
 }
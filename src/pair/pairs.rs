@@ -0,0 +1,66 @@
+use crate::pair::step::Step;
+use crate::pair::steps::Steps;
+
+// The positions in x and y of each column of an alignment, with `None` for
+// the gapped side. Transpositions make up two columns.
+pub struct Pairs<'a> {
+    inner: Steps<'a>,
+    transposed: Option<(usize, usize)>,
+}
+
+impl<'a> Pairs<'a> {
+    pub fn new(inner: Steps<'a>) -> Pairs<'a> {
+        Pairs {
+            inner,
+            transposed: None,
+        }
+    }
+}
+
+impl<'a> Iterator for Pairs<'a> {
+    type Item = (Option<usize>, Option<usize>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some((x, y)) = self.transposed.take() {
+            return Some((Some(x), Some(y)));
+        }
+        self.inner.next().map(|step| match step {
+            Step::Align { x, y } => (Some(x), Some(y)),
+            Step::Delete { x } => (Some(x), None),
+            Step::Insert { y } => (None, Some(y)),
+            Step::Transpose { x, y } => {
+                self.transposed = Some((x + 1, y + 1));
+                (Some(x), Some(y))
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pair::alignment::Alignment;
+    use crate::pair::cursor::Cursor;
+    use crate::pair::step_mask::StepMask;
+
+    #[test]
+    fn pairs_work() {
+        let steps = vec![
+            StepMask::ALIGN,
+            StepMask::DELETE,
+            StepMask::TRANSPOSE,
+            StepMask::INSERT,
+        ];
+        let alignment = Alignment::new(Cursor { x: 1, y: 0 }, steps, 0);
+        let pairs: Vec<_> = alignment.pairs().collect();
+        assert_eq!(
+            pairs,
+            vec![
+                (Some(1), Some(0)),
+                (Some(2), None),
+                (Some(3), Some(1)),
+                (Some(4), Some(2)),
+                (None, Some(3)),
+            ]
+        );
+    }
+}
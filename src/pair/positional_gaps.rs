@@ -0,0 +1,125 @@
+use crate::pair::boundary::Boundary;
+use crate::pair::free_ends::FreeEnds;
+use crate::pair::step_mask::StepMask;
+use crate::pair::strategy::Strategy;
+
+// Scores gaps of the wrapped strategy by position, e.g. to make them cheaper
+// in homopolymer runs or low-confidence regions. `delete(x, y)` scores
+// deleting x[x] after y[..y] got aligned, `insert(x, y)` inserting y[y]
+// after x[..x].
+#[derive(Clone, Debug)]
+pub struct PositionalGaps<S, D, I> {
+    strategy: S,
+    delete: D,
+    insert: I,
+}
+
+impl<S, D, I> PositionalGaps<S, D, I>
+where
+    S: Strategy,
+    D: Fn(usize, usize) -> isize + Clone,
+    I: Fn(usize, usize) -> isize + Clone,
+{
+    pub fn new(strategy: S, delete: D, insert: I) -> PositionalGaps<S, D, I> {
+        PositionalGaps {
+            strategy,
+            delete,
+            insert,
+        }
+    }
+}
+
+impl<S, D, I> Strategy for PositionalGaps<S, D, I>
+where
+    S: Strategy,
+    D: Fn(usize, usize) -> isize + Clone,
+    I: Fn(usize, usize) -> isize + Clone,
+{
+    fn match_score(&self) -> isize {
+        self.strategy.match_score()
+    }
+
+    fn mismatch_score(&self) -> isize {
+        self.strategy.mismatch_score()
+    }
+
+    fn insert_score(&self) -> isize {
+        self.strategy.insert_score()
+    }
+
+    fn delete_score(&self) -> isize {
+        self.strategy.delete_score()
+    }
+
+    fn delete_score_at(&self, x: usize, y: usize) -> isize {
+        (self.delete)(x, y)
+    }
+
+    fn insert_score_at(&self, x: usize, y: usize) -> isize {
+        (self.insert)(x, y)
+    }
+
+    fn terminal_delete_score_at(&self, x: usize, y: usize) -> isize {
+        (self.delete)(x, y)
+    }
+
+    fn terminal_insert_score_at(&self, x: usize, y: usize) -> isize {
+        (self.insert)(x, y)
+    }
+
+    fn total_score(&self, score: isize) -> isize {
+        self.strategy.total_score(score)
+    }
+
+    fn bandwidth(&self) -> Option<usize> {
+        self.strategy.bandwidth()
+    }
+
+    fn free_ends(&self) -> FreeEnds {
+        self.strategy.free_ends()
+    }
+
+    fn first_row(&self) -> Boundary {
+        self.strategy.first_row()
+    }
+
+    fn first_column(&self) -> Boundary {
+        self.strategy.first_column()
+    }
+
+    fn circular(&self) -> bool {
+        self.strategy.circular()
+    }
+
+    fn step_mask(&self, align: isize, insert: isize, delete: isize) -> StepMask {
+        self.strategy.step_mask(align, insert, delete)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pair::alignment_matrix::in_memory::AlignmentMatrix as InMemoryAlignmentMatrix;
+    use crate::pair::alignment_set::AlignmentSet;
+    use crate::pair::needleman_wunsch::NeedlemanWunsch;
+
+    #[test]
+    fn homopolymer_gaps_are_cheap() {
+        let x = b"ACGTTTTTACG";
+        let y = b"ACGTTTACG";
+        let strategy = NeedlemanWunsch::new(1, -1, -3, -3);
+        let set: AlignmentSet<InMemoryAlignmentMatrix> =
+            AlignmentSet::new(x.len(), y.len(), strategy.clone(), |i, j| x[i] == y[j]).unwrap();
+        assert_eq!(set.global_score(), 9 - 6);
+
+        let homopolymer = |i: usize| (i > 0 && x[i - 1] == x[i]) || x.get(i + 1) == Some(&x[i]);
+        let strategy = PositionalGaps::new(
+            strategy,
+            |i, _| if homopolymer(i) { 0 } else { -3 },
+            |_, _| -3,
+        );
+        let set: AlignmentSet<InMemoryAlignmentMatrix> =
+            AlignmentSet::new(x.len(), y.len(), strategy, |i, j| x[i] == y[j]).unwrap();
+        assert_eq!(set.global_score(), 9);
+    }
+}
@@ -0,0 +1,221 @@
+use crate::pair::alignment::Alignment;
+use crate::pair::alignments::Preference;
+use crate::pair::score_table;
+use crate::pair::step::Step;
+use crate::pair::step_mask::StepMask;
+use crate::pair::strategy::Strategy;
+
+#[derive(Debug, PartialEq)]
+pub enum ProfileError {
+    // The row at `index` is not as long as the first one.
+    RowLength { index: usize },
+}
+
+// Rows of equal length, `None` marking a gap. Columns get compared by
+// their sum-of-pairs score, i.e. the expected pairwise score times the
+// number of row pairs.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Profile<T> {
+    rows: Vec<Vec<Option<T>>>,
+}
+
+impl<T> Profile<T>
+where
+    T: Clone + PartialEq,
+{
+    pub fn new(sequence: &[T]) -> Profile<T> {
+        Profile {
+            rows: vec![sequence.iter().cloned().map(Some).collect()],
+        }
+    }
+
+    pub fn from_rows(rows: Vec<Vec<Option<T>>>) -> Result<Profile<T>, ProfileError> {
+        let len = rows.first().map_or(0, |row| row.len());
+        match rows.iter().position(|row| row.len() != len) {
+            Some(index) => Err(ProfileError::RowLength { index }),
+            None => Ok(Profile { rows }),
+        }
+    }
+
+    pub fn rows(&self) -> &[Vec<Option<T>>] {
+        &self.rows
+    }
+
+    pub fn depth(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn len(&self) -> usize {
+        self.rows.first().map_or(0, |row| row.len())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // Symbol counts of a column, plus the number of gaps.
+    pub fn column(&self, index: usize) -> (Vec<(T, usize)>, usize) {
+        let mut counts: Vec<(T, usize)> = vec![];
+        let mut gaps = 0;
+        for row in self.rows.iter() {
+            match row[index] {
+                Some(ref symbol) => match counts.iter_mut().find(|(s, _)| s == symbol) {
+                    Some(count) => count.1 += 1,
+                    None => counts.push((symbol.clone(), 1)),
+                },
+                None => gaps += 1,
+            }
+        }
+        (counts, gaps)
+    }
+
+    // Global alignment of the columns of `self` (x) and `other` (y).
+    pub fn align<S: Strategy>(&self, other: &Profile<T>, strategy: &S) -> Alignment {
+        self.align_preferring(other, strategy, Preference::default())
+    }
+
+    // Like `align`, following co-optimal steps in the order of `preference`.
+    pub fn align_preferring<S: Strategy>(
+        &self,
+        other: &Profile<T>,
+        strategy: &S,
+        preference: Preference,
+    ) -> Alignment {
+        let xs: Vec<_> = (0..self.len()).map(|i| self.column(i)).collect();
+        let ys: Vec<_> = (0..other.len()).map(|j| other.column(j)).collect();
+        let symbols = |column: &(Vec<(T, usize)>, usize)| -> isize {
+            column.0.iter().map(|&(_, count)| count as isize).sum()
+        };
+        // Every symbol of a column set against a gap column consumes from
+        // the other profile.
+        let x_depth = self.depth() as isize;
+        let y_depth = other.depth() as isize;
+        let delete = |i: usize| symbols(&xs[i]) * y_depth * strategy.delete_score();
+        let insert = |j: usize| symbols(&ys[j]) * x_depth * strategy.insert_score();
+        let substitute = |i: usize, j: usize| {
+            let (ref x_counts, x_gaps) = xs[i];
+            let (ref y_counts, y_gaps) = ys[j];
+            let mut score = 0;
+            for (a, a_count) in x_counts.iter() {
+                for (b, b_count) in y_counts.iter() {
+                    let pair = if a == b {
+                        strategy.match_score()
+                    } else {
+                        strategy.mismatch_score()
+                    };
+                    score += pair * (*a_count * *b_count) as isize;
+                }
+            }
+            score += symbols(&xs[i]) * y_gaps as isize * strategy.delete_score();
+            score += symbols(&ys[j]) * x_gaps as isize * strategy.insert_score();
+            score
+        };
+
+        score_table::global_alignment(self.len(), other.len(), &preference, |x, y, candidate| {
+            if x > 0 && y > 0 {
+                candidate(substitute(x - 1, y - 1), StepMask::ALIGN, 1);
+            }
+            if x > 0 {
+                candidate(delete(x - 1), StepMask::DELETE, 1);
+            }
+            if y > 0 {
+                candidate(insert(y - 1), StepMask::INSERT, 1);
+            }
+        })
+    }
+
+    // Stacks the rows of both profiles, padding them with gaps according to
+    // an alignment of `self` (x) and `other` (y).
+    pub fn merge(&self, other: &Profile<T>, alignment: &Alignment) -> Profile<T> {
+        let mut rows: Vec<Vec<Option<T>>> = vec![vec![]; self.depth() + other.depth()];
+        let (top, bottom) = rows.split_at_mut(self.depth());
+        for step in alignment.steps() {
+            // Columns cannot swap elements, so the two of a transposition
+            // are merged as aligned.
+            let columns = match step {
+                Step::Align { x, y } => vec![(Some(x), Some(y))],
+                Step::Delete { x } => vec![(Some(x), None)],
+                Step::Insert { y } => vec![(None, Some(y))],
+                Step::Transpose { x, y } => vec![(Some(x), Some(y)), (Some(x + 1), Some(y + 1))],
+            };
+            for (x, y) in columns {
+                for (row, source) in top.iter_mut().zip(self.rows.iter()) {
+                    row.push(x.and_then(|x| source[x].clone()));
+                }
+                for (row, source) in bottom.iter_mut().zip(other.rows.iter()) {
+                    row.push(y.and_then(|y| source[y].clone()));
+                }
+            }
+        }
+        Profile { rows }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pair::cursor::Cursor;
+    use crate::pair::needleman_wunsch::NeedlemanWunsch;
+
+    fn profile(rows: &[&str]) -> Profile<u8> {
+        Profile::from_rows(
+            rows.iter()
+                .map(|row| {
+                    row.bytes()
+                        .map(|b| if b == b'-' { None } else { Some(b) })
+                        .collect()
+                })
+                .collect(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn single_sequences_align_like_pairwise() {
+        let strategy = NeedlemanWunsch::new(1, -1, -1, -1);
+        let x = Profile::new(b"GATTACA");
+        let y = Profile::new(b"GCATGCU");
+        assert_eq!(x.align(&y, &strategy).score(), 0);
+    }
+
+    #[test]
+    fn profiles_are_merged() {
+        let strategy = NeedlemanWunsch::new(2, -1, -2, -2);
+        let x = profile(&["ACGT", "A-GT"]);
+        let y = profile(&["AGT", "ACT"]);
+        let alignment = x.align(&y, &strategy);
+        let merged = x.merge(&y, &alignment);
+        assert_eq!(merged, profile(&["ACGT", "A-GT", "A-GT", "A-CT"]));
+        assert_eq!(merged.column(1), (vec![(b'C', 1)], 3));
+    }
+
+    #[test]
+    fn preference_places_gaps() {
+        let strategy = NeedlemanWunsch::new(1, -1, -1, -1);
+        let x = Profile::new(b"ACCGT");
+        let y = Profile::new(b"ACGT");
+        let merged = |preference| {
+            let alignment = x.align_preferring(&y, &strategy, preference);
+            x.merge(&y, &alignment)
+        };
+        assert_eq!(merged(Preference::GAPS_RIGHT), profile(&["ACCGT", "AC-GT"]));
+        assert_eq!(merged(Preference::GAPS_LEFT), profile(&["ACCGT", "A-CGT"]));
+    }
+
+    #[test]
+    fn uneven_rows_are_rejected() {
+        let rows = vec![vec![Some(b'A'), None], vec![Some(b'A')]];
+        assert_eq!(
+            Profile::from_rows(rows).err(),
+            Some(ProfileError::RowLength { index: 1 })
+        );
+    }
+
+    #[test]
+    fn transpositions_are_merged_as_aligned() {
+        let x = Profile::new(b"AB");
+        let y = Profile::new(b"BA");
+        let alignment = Alignment::new(Cursor { x: 0, y: 0 }, vec![StepMask::TRANSPOSE], 0);
+        assert_eq!(x.merge(&y, &alignment), profile(&["AB", "BA"]));
+    }
+}
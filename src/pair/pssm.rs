@@ -0,0 +1,150 @@
+use crate::pair::alignment_matrix::AlignmentMatrix;
+use crate::pair::alignment_set::{AlignmentSet, ScoreError};
+use crate::pair::free_ends::FreeEnds;
+use crate::pair::step_mask::StepMask;
+use crate::pair::strategy::Strategy;
+
+#[derive(Debug, PartialEq)]
+pub enum PssmError {
+    // The column at `index` does not score every symbol of the alphabet.
+    ColumnLength { index: usize },
+}
+
+// A position-specific scoring matrix taking the place of x: `columns[i][a]`
+// scores symbol `alphabet[a]` at position `i`. Symbols outside of the
+// alphabet get the lowest score of the column.
+#[derive(Clone, Debug)]
+pub struct Pssm<T> {
+    alphabet: Vec<T>,
+    columns: Vec<Vec<isize>>,
+    pub insert: isize,
+    pub delete: isize,
+    pub free_ends: FreeEnds,
+}
+
+impl<T> Pssm<T>
+where
+    T: Clone + PartialEq,
+{
+    pub fn new(
+        alphabet: Vec<T>,
+        columns: Vec<Vec<isize>>,
+        insert: isize,
+        delete: isize,
+    ) -> Result<Pssm<T>, PssmError> {
+        if let Some(index) = columns
+            .iter()
+            .position(|column| column.len() != alphabet.len())
+        {
+            return Err(PssmError::ColumnLength { index });
+        }
+        Ok(Pssm {
+            alphabet,
+            columns,
+            insert,
+            delete,
+            free_ends: FreeEnds::empty(),
+        })
+    }
+
+    pub fn with_free_ends(mut self, free_ends: FreeEnds) -> Pssm<T> {
+        self.free_ends = free_ends;
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.columns.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.columns.is_empty()
+    }
+
+    pub fn score(&self, position: usize, symbol: &T) -> isize {
+        let column = &self.columns[position];
+        match self.alphabet.iter().position(|a| a == symbol) {
+            Some(a) => column[a],
+            None => column.iter().cloned().min().unwrap_or(0),
+        }
+    }
+
+    // Aligns the profile (x) against `sequence` (y).
+    pub fn alignment_set<M, E>(&self, sequence: &[T]) -> Result<AlignmentSet<M>, ScoreError<E>>
+    where
+        M: AlignmentMatrix<Error = E>,
+    {
+        AlignmentSet::builder(self.len(), sequence.len(), self.clone())
+            .build_with_scores(|x, y| self.score(x, &sequence[y]))
+    }
+}
+
+impl<T> Strategy for Pssm<T>
+where
+    T: Clone,
+{
+    // Substitutions are scored per position, see `Pssm::score`.
+    fn match_score(&self) -> isize {
+        0
+    }
+
+    fn mismatch_score(&self) -> isize {
+        0
+    }
+
+    fn insert_score(&self) -> isize {
+        self.insert
+    }
+
+    fn delete_score(&self) -> isize {
+        self.delete
+    }
+
+    fn total_score(&self, score: isize) -> isize {
+        score
+    }
+
+    fn free_ends(&self) -> FreeEnds {
+        self.free_ends
+    }
+
+    fn step_mask(&self, align: isize, insert: isize, delete: isize) -> StepMask {
+        StepMask::from_scores(align, delete, insert)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pair::alignment_matrix::in_memory::AlignmentMatrix as InMemoryAlignmentMatrix;
+    use crate::pair::cursor::Cursor;
+
+    fn motif() -> Pssm<u8> {
+        // Scores for A, C, G, T of the motif `[AG]C[GT]`.
+        let columns = vec![vec![3, -2, 3, -2], vec![-2, 4, -2, -2], vec![-2, -2, 2, 2]];
+        Pssm::new(b"ACGT".to_vec(), columns, -3, -3).unwrap()
+    }
+
+    #[test]
+    fn profile_alignment_works() {
+        let set: AlignmentSet<InMemoryAlignmentMatrix> = motif().alignment_set(b"GCT").unwrap();
+        assert_eq!(set.global_score(), 9);
+        let set: AlignmentSet<InMemoryAlignmentMatrix> = motif().alignment_set(b"GNT").unwrap();
+        assert_eq!(set.global_score(), 3);
+    }
+
+    #[test]
+    fn short_columns_are_rejected() {
+        let columns = vec![vec![3, -2, 3, -2], vec![-2, 4, -2]];
+        let result = Pssm::new(b"ACGT".to_vec(), columns, -3, -3);
+        assert_eq!(result.err(), Some(PssmError::ColumnLength { index: 1 }));
+    }
+
+    #[test]
+    fn motif_search_works() {
+        let motif = motif().with_free_ends(FreeEnds::LEADING_Y | FreeEnds::TRAILING_Y);
+        let set: AlignmentSet<InMemoryAlignmentMatrix> =
+            motif.alignment_set(b"TTTTACGTTTT").unwrap();
+        assert_eq!(set.global_score(), 9);
+        assert_eq!(set.global_alignment().origin(), &Cursor { x: 0, y: 4 });
+    }
+}
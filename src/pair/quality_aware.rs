@@ -0,0 +1,131 @@
+use crate::pair::alignment_matrix::AlignmentMatrix;
+use crate::pair::alignment_set::{AlignmentSet, ScoreError};
+use crate::pair::step_mask::StepMask;
+use crate::pair::strategy::Strategy;
+
+#[derive(Debug)]
+pub enum QualityError<E> {
+    // There are `qualities` qualities for the `y_len` bases of y.
+    LengthMismatch { qualities: usize, y_len: usize },
+    Score(ScoreError<E>),
+}
+
+// Global alignment of a read (y) with per-base Phred qualities. A mismatch
+// at a base with error probability `p` only costs `1 - p` of the mismatch
+// score, so likely sequencing errors weigh less than real differences.
+#[derive(Clone, Debug)]
+pub struct QualityAware {
+    pub equal: isize,
+    pub align: isize,
+    pub insert: isize,
+    pub delete: isize,
+}
+
+impl QualityAware {
+    pub fn new(equal: isize, align: isize, insert: isize, delete: isize) -> QualityAware {
+        QualityAware {
+            equal,
+            align,
+            insert,
+            delete,
+        }
+    }
+
+    // `quality` is the Phred score, not its ASCII encoding.
+    pub fn mismatch_score_at(&self, quality: u8) -> isize {
+        let error = 10f64.powf(-f64::from(quality) / 10.0);
+        (self.align as f64 * (1.0 - error)).round() as isize
+    }
+
+    pub fn alignment_set<M, E, F>(
+        &self,
+        x_len: usize,
+        y_len: usize,
+        qualities: &[u8],
+        f: F,
+    ) -> Result<AlignmentSet<M>, QualityError<E>>
+    where
+        M: AlignmentMatrix<Error = E>,
+        F: Fn(usize, usize) -> bool,
+    {
+        if qualities.len() != y_len {
+            return Err(QualityError::LengthMismatch {
+                qualities: qualities.len(),
+                y_len,
+            });
+        }
+        let mismatch: Vec<isize> = qualities
+            .iter()
+            .map(|&quality| self.mismatch_score_at(quality))
+            .collect();
+        AlignmentSet::builder(x_len, y_len, self.clone())
+            .build_with_scores(|x, y| if f(x, y) { self.equal } else { mismatch[y] })
+            .map_err(QualityError::Score)
+    }
+}
+
+impl Strategy for QualityAware {
+    fn match_score(&self) -> isize {
+        self.equal
+    }
+
+    fn mismatch_score(&self) -> isize {
+        self.align
+    }
+
+    fn insert_score(&self) -> isize {
+        self.insert
+    }
+
+    fn delete_score(&self) -> isize {
+        self.delete
+    }
+
+    fn total_score(&self, score: isize) -> isize {
+        score
+    }
+
+    fn step_mask(&self, align: isize, insert: isize, delete: isize) -> StepMask {
+        StepMask::from_scores(align, delete, insert)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pair::alignment_matrix::in_memory::AlignmentMatrix as InMemoryAlignmentMatrix;
+
+    #[test]
+    fn low_quality_mismatches_are_cheap() {
+        let strategy = QualityAware::new(1, -4, -4, -4);
+        assert_eq!(strategy.mismatch_score_at(40), -4);
+        assert_eq!(strategy.mismatch_score_at(3), -2);
+        assert_eq!(strategy.mismatch_score_at(0), 0);
+
+        let x = b"ACGTACGT";
+        let y = b"ACGAACGT";
+        let set: AlignmentSet<InMemoryAlignmentMatrix> = strategy
+            .alignment_set(x.len(), y.len(), &[40; 8], |i, j| x[i] == y[j])
+            .unwrap();
+        assert_eq!(set.global_score(), 7 - 4);
+        let qualities = [40, 40, 40, 3, 40, 40, 40, 40];
+        let set: AlignmentSet<InMemoryAlignmentMatrix> = strategy
+            .alignment_set(x.len(), y.len(), &qualities, |i, j| x[i] == y[j])
+            .unwrap();
+        assert_eq!(set.global_score(), 7 - 2);
+    }
+
+    #[test]
+    fn missing_qualities_are_reported() {
+        let strategy = QualityAware::new(1, -4, -4, -4);
+        let x = b"ACGT";
+        let result: Result<AlignmentSet<InMemoryAlignmentMatrix>, _> =
+            strategy.alignment_set(x.len(), x.len(), &[40; 3], |i, j| x[i] == x[j]);
+        match result {
+            Err(QualityError::LengthMismatch { qualities, y_len }) => {
+                assert_eq!((qualities, y_len), (3, 4))
+            }
+            _ => panic!("Length mismatch not detected."),
+        }
+    }
+}
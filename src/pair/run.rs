@@ -7,6 +7,7 @@ pub enum Run {
     Align { x: Range<usize>, y: Range<usize> },
     Delete { x: Range<usize> },
     Insert { y: Range<usize> },
+    Transpose { x: Range<usize>, y: Range<usize> },
 }
 
 impl Run {
@@ -15,6 +16,7 @@ impl Run {
             Run::Align { x: _, y: _ } => StepMask::ALIGN,
             Run::Delete { x: _ } => StepMask::DELETE,
             Run::Insert { y: _ } => StepMask::INSERT,
+            Run::Transpose { x: _, y: _ } => StepMask::TRANSPOSE,
         }
     }
 }
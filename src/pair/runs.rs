@@ -9,7 +9,7 @@ pub struct Runs<'a> {
 }
 
 impl<'a> Runs<'a> {
-    pub fn new(inner: Peekable<Steps<'a>>) -> Runs {
+    pub fn new(inner: Peekable<Steps<'a>>) -> Runs<'a> {
         Runs { inner }
     }
 }
@@ -22,19 +22,9 @@ impl<'a> Iterator for Runs<'a> {
         let run = inner.peek().map(|step| step.to_run());
         run.map(|mut run| {
             let mask = run.mask();
-            let mut peekable = inner.peekable();
-            let mut cautious_take_while = || {
-                let is_match = match peekable.peek() {
-                    Some(step) => step.mask() == mask,
-                    None => false,
-                };
-                if is_match {
-                    peekable.next()
-                } else {
-                    None
-                }
-            };
-            while let Some(step) = cautious_take_while() {
+            // The first step not matching has to stay in `inner` for the
+            // next run.
+            while let Some(step) = inner.next_if(|step| step.mask() == mask) {
                 run = match (run, step) {
                     (
                         Run::Align { x: run_x, y: run_y },
@@ -52,6 +42,16 @@ impl<'a> Iterator for Runs<'a> {
                     (Run::Insert { y: run_y }, Step::Insert { y: step_y }) => Run::Insert {
                         y: (run_y.start)..(step_y + 1),
                     },
+                    (
+                        Run::Transpose { x: run_x, y: run_y },
+                        Step::Transpose {
+                            x: step_x,
+                            y: step_y,
+                        },
+                    ) => Run::Transpose {
+                        x: (run_x.start)..(step_x + 2),
+                        y: (run_y.start)..(step_y + 2),
+                    },
                     _ => unreachable!(),
                 }
             }
@@ -0,0 +1,96 @@
+use std::ops::Range;
+
+use crate::pair::strategy::Strategy;
+
+#[derive(Clone, Debug)]
+struct Override {
+    // Whether `range` refers to positions of x or of y.
+    x: bool,
+    range: Range<usize>,
+    equal: isize,
+    align: isize,
+}
+
+// Match and mismatch scores for intervals of x or y, overriding those of the
+// strategy. Of overlapping intervals the one registered last wins.
+#[derive(Clone, Debug, Default)]
+pub struct ScoreOverrides {
+    overrides: Vec<Override>,
+}
+
+impl ScoreOverrides {
+    pub fn new() -> ScoreOverrides {
+        ScoreOverrides::default()
+    }
+
+    pub fn x(mut self, range: Range<usize>, equal: isize, align: isize) -> ScoreOverrides {
+        self.overrides.push(Override {
+            x: true,
+            range,
+            equal,
+            align,
+        });
+        self
+    }
+
+    pub fn y(mut self, range: Range<usize>, equal: isize, align: isize) -> ScoreOverrides {
+        self.overrides.push(Override {
+            x: false,
+            range,
+            equal,
+            align,
+        });
+        self
+    }
+
+    // Takes the scores for x[i] and y[j] from the last override covering i or
+    // j, falling back to the strategy's.
+    pub fn scores<'a, S, F>(&'a self, strategy: &'a S, f: F) -> impl Fn(usize, usize) -> isize + 'a
+    where
+        S: Strategy,
+        F: Fn(usize, usize) -> bool + 'a,
+    {
+        move |i, j| {
+            let scores = self
+                .overrides
+                .iter()
+                .rev()
+                .find(|o| o.range.contains(if o.x { &i } else { &j }))
+                .map_or((strategy.match_score(), strategy.mismatch_score()), |o| {
+                    (o.equal, o.align)
+                });
+            if f(i, j) {
+                scores.0
+            } else {
+                scores.1
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pair::alignment_matrix::in_memory::AlignmentMatrix as InMemoryAlignmentMatrix;
+    use crate::pair::alignment_set::AlignmentSet;
+    use crate::pair::needleman_wunsch::NeedlemanWunsch;
+
+    #[test]
+    fn overrides_apply_to_intervals() {
+        let x = b"ACGTACGT";
+        let y = b"AGGTACCT";
+        let strategy = NeedlemanWunsch::new(1, -1, -3, -3);
+        let score = |overrides: &ScoreOverrides| {
+            let set: AlignmentSet<InMemoryAlignmentMatrix> =
+                AlignmentSet::builder(x.len(), y.len(), strategy.clone())
+                    .build_with_scores(overrides.scores(&strategy, |i, j| x[i] == y[j]))
+                    .unwrap();
+            set.global_score()
+        };
+        assert_eq!(score(&ScoreOverrides::new()), 6 - 2);
+        // Doubles the mismatch penalty in the second half of x.
+        assert_eq!(score(&ScoreOverrides::new().x(4..8, 1, -2)), 6 - 3);
+        let overrides = ScoreOverrides::new().x(0..8, 1, -2).y(0..2, 5, 0);
+        assert_eq!(score(&overrides), 5 + 5 - 2);
+    }
+}
@@ -0,0 +1,64 @@
+use crate::pair::alignment::Alignment;
+use crate::pair::alignments::Preference;
+use crate::pair::cursor::Cursor;
+use crate::pair::step_mask::StepMask;
+
+// Global alignment over a table keeping the score of every cell and the
+// step leading into it, for aligners whose recurrences do not fit
+// `AlignmentSet`. For every cell `candidates(x, y, candidate)` offers the
+// ways of reaching it as `candidate(score, step, len)`: `len` times `step`
+// from the cell they start at, adding `score` to its score, saturating.
+// Of several best candidates the step coming first in `preference` wins,
+// then the first one offered. Cells without any candidate, like the origin,
+// score 0 and end the traceback.
+pub(crate) fn global_alignment<C>(
+    x_len: usize,
+    y_len: usize,
+    preference: &Preference,
+    candidates: C,
+) -> Alignment
+where
+    C: Fn(usize, usize, &mut dyn FnMut(isize, StepMask, usize)),
+{
+    let width = x_len + 1;
+    let mut scores: Vec<isize> = vec![0; width * (y_len + 1)];
+    let mut steps = vec![(StepMask::STOP, 0); width * (y_len + 1)];
+    for y in 0..=y_len {
+        for x in 0..=x_len {
+            let mut best: Option<(isize, StepMask, usize)> = None;
+            candidates(x, y, &mut |score, step, len| {
+                let mut start = Cursor { x, y };
+                for _ in 0..len {
+                    start.apply_backwards_step(step);
+                }
+                let score = scores[start.y * width + start.x].saturating_add(score);
+                let better = |(best_score, best_step, _): (isize, StepMask, usize)| {
+                    score > best_score
+                        || (score == best_score && preference.prefers(step, best_step))
+                };
+                if best.is_none_or(better) {
+                    best = Some((score, step, len));
+                }
+            });
+            if let Some((score, step, len)) = best {
+                scores[y * width + x] = score;
+                steps[y * width + x] = (step, len);
+            }
+        }
+    }
+
+    let mut cursor = Cursor { x: x_len, y: y_len };
+    let mut path = vec![];
+    loop {
+        let (step, len) = steps[cursor.y * width + cursor.x];
+        if step == StepMask::STOP {
+            break;
+        }
+        for _ in 0..len {
+            path.push(step);
+            cursor.apply_backwards_step(step);
+        }
+    }
+    path.reverse();
+    Alignment::new(cursor, path, scores[y_len * width + x_len])
+}
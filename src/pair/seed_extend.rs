@@ -0,0 +1,320 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::marker::PhantomData;
+
+use crate::pair::alignment::Alignment;
+use crate::pair::alignment_matrix::AlignmentMatrix;
+use crate::pair::alignment_set::{AlignmentSet, ScoreError};
+use crate::pair::cursor::Cursor;
+use crate::pair::needleman_wunsch::NeedlemanWunsch;
+use crate::pair::step_mask::StepMask;
+use crate::pair::strategy::Strategy;
+use crate::pair::x_drop::XDrop;
+
+// An exact match of `len` elements starting at `x` and `y`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Seed {
+    pub x: usize,
+    pub y: usize,
+    pub len: usize,
+}
+
+// Positions of all k-mers of a sequence.
+#[derive(Clone, Debug)]
+pub struct KmerIndex<'a, T: 'a> {
+    k: usize,
+    kmers: HashMap<&'a [T], Vec<usize>>,
+}
+
+impl<'a, T> KmerIndex<'a, T>
+where
+    T: Eq + Hash,
+{
+    // Returns `None` if `k` is zero.
+    pub fn new(sequence: &'a [T], k: usize) -> Option<KmerIndex<'a, T>> {
+        if k == 0 {
+            return None;
+        }
+        let mut kmers: HashMap<&'a [T], Vec<usize>> = HashMap::new();
+        for (x, kmer) in sequence.windows(k).enumerate() {
+            kmers.entry(kmer).or_default().push(x);
+        }
+        Some(KmerIndex { k, kmers })
+    }
+
+    // Hits of k-mers of `query` (y) in the indexed sequence (x), merged
+    // along their diagonals into maximal seeds.
+    pub fn seeds(&self, query: &[T]) -> Vec<Seed> {
+        let mut seeds: Vec<Seed> = vec![];
+        let mut open: HashMap<isize, usize> = HashMap::new();
+        for (y, kmer) in query.windows(self.k).enumerate() {
+            for &x in self.kmers.get(kmer).into_iter().flatten() {
+                let diagonal = x as isize - y as isize;
+                match open.get(&diagonal) {
+                    Some(&i) if seeds[i].y + seeds[i].len == y + self.k - 1 => {
+                        seeds[i].len += 1;
+                    }
+                    _ => {
+                        open.insert(diagonal, seeds.len());
+                        seeds.push(Seed { x, y, len: self.k });
+                    }
+                }
+            }
+        }
+        seeds
+    }
+}
+
+// The colinear, non-overlapping subset of `seeds` covering the most elements.
+// Seeds are visited by their start in x. Before each, the seeds ending at or
+// before it in x enter a Fenwick tree over their ends in y, which holds the
+// best chains ending there, so its best predecessor is found in O(log n).
+pub fn chain(seeds: &[Seed]) -> Vec<Seed> {
+    let mut seeds = seeds.to_vec();
+    seeds.retain(|seed| seed.len > 0);
+    seeds.sort_by_key(|seed| (seed.x, seed.y));
+    let mut ends: Vec<usize> = (0..seeds.len()).collect();
+    ends.sort_by_key(|&i| seeds[i].x + seeds[i].len);
+    let mut y_ends: Vec<usize> = seeds.iter().map(|seed| seed.y + seed.len).collect();
+    y_ends.sort_unstable();
+    y_ends.dedup();
+
+    // Ties are broken towards the first seed.
+    let mut tree: Vec<Option<(usize, Reverse<usize>)>> = vec![None; y_ends.len() + 1];
+    let mut best: Vec<(usize, Option<usize>)> = vec![];
+    let mut ends = ends.into_iter().peekable();
+    for seed in seeds.iter() {
+        while let Some(j) = ends.next_if(|&j| seeds[j].x + seeds[j].len <= seed.x) {
+            let entry = Some((best[j].0, Reverse(j)));
+            let mut k = y_ends.partition_point(|&y| y < seeds[j].y + seeds[j].len) + 1;
+            while k < tree.len() {
+                tree[k] = tree[k].max(entry);
+                k += k & k.wrapping_neg();
+            }
+        }
+        let mut previous = None;
+        let mut k = y_ends.partition_point(|&y| y <= seed.y);
+        while k > 0 {
+            previous = previous.max(tree[k]);
+            k -= k & k.wrapping_neg();
+        }
+        best.push(match previous {
+            Some((len, Reverse(j))) => (len + seed.len, Some(j)),
+            None => (seed.len, None),
+        });
+    }
+    let mut chain = vec![];
+    let mut i = (0..seeds.len()).max_by_key(|&i| (best[i].0, usize::MAX - i));
+    while let Some(j) = i {
+        chain.push(seeds[j]);
+        i = best[j].1;
+    }
+    chain.reverse();
+    chain
+}
+
+#[derive(Debug)]
+pub enum SeedExtendError<E> {
+    // Filling the gap between two seeds failed.
+    Score(ScoreError<E>),
+}
+
+// Heuristic local alignment for long sequences: k-mer seeds are chained,
+// the gaps between them are filled by banded alignment and the ends of the
+// chain get extended by X-drop. Seeds whose diagonals differ by more than
+// the bandwidth are not bridged, so every gap stays within its band; of the
+// parts of the chain this splits it into, the one covering the most
+// elements gets aligned. A banded `M` keeps the memory of a gap within the
+// band, too.
+#[derive(Clone, Debug)]
+pub struct SeedExtend<M, S> {
+    strategy: S,
+    k: usize,
+    bandwidth: usize,
+    x_drop: isize,
+    matrix: PhantomData<M>,
+}
+
+impl<M, S, E> SeedExtend<M, S>
+where
+    M: AlignmentMatrix<Error = E>,
+    S: Strategy,
+{
+    // Returns `None` if `k` is zero.
+    pub fn new(strategy: S, k: usize, bandwidth: usize, x_drop: isize) -> Option<SeedExtend<M, S>> {
+        if k == 0 {
+            return None;
+        }
+        Some(SeedExtend {
+            strategy,
+            k,
+            bandwidth,
+            x_drop,
+            matrix: PhantomData,
+        })
+    }
+
+    // The longest part of the chain whose consecutive seeds lie within the
+    // bandwidth of each other's diagonal, the first of equally long ones.
+    fn bridgeable<'c>(&self, chain: &'c [Seed]) -> &'c [Seed] {
+        let diagonal = |seed: &Seed| seed.x as isize - seed.y as isize;
+        let len = |part: &[Seed]| part.iter().map(|seed| seed.len).sum::<usize>();
+        let mut best: &[Seed] = &[];
+        for part in chain.chunk_by(|a, b| diagonal(a).abs_diff(diagonal(b)) <= self.bandwidth) {
+            if len(part) > len(best) {
+                best = part;
+            }
+        }
+        best
+    }
+
+    // Returns `None` if the sequences share no k-mer. The gaps between
+    // seeds get filled in matrices of type `M`.
+    pub fn align<T>(&self, x: &[T], y: &[T]) -> Result<Option<Alignment>, SeedExtendError<E>>
+    where
+        T: Eq + Hash,
+    {
+        let chain = match KmerIndex::new(x, self.k) {
+            Some(index) => chain(&index.seeds(y)),
+            None => return Ok(None),
+        };
+        let chain = self.bridgeable(&chain);
+        let first = match chain.first() {
+            Some(first) => first,
+            None => return Ok(None),
+        };
+        let f = |i: usize, j: usize| x[i] == y[j];
+        let strategy = &self.strategy;
+
+        let x_drop = XDrop::new(strategy.clone(), self.x_drop);
+        let head = x_drop.extend(
+            first.x,
+            first.y,
+            Cursor {
+                x: first.x,
+                y: first.y,
+            },
+            f,
+        );
+        let mut steps: Vec<StepMask> = head.steps().map(|step| step.mask()).collect();
+        let mut score = head.score();
+
+        let mut end = Cursor {
+            x: first.x,
+            y: first.y,
+        };
+        for seed in chain.iter() {
+            let fill = NeedlemanWunsch::new(
+                strategy.match_score(),
+                strategy.mismatch_score(),
+                strategy.insert_score(),
+                strategy.delete_score(),
+            )
+            .with_bandwidth(self.bandwidth);
+            let set: AlignmentSet<M> =
+                AlignmentSet::new(seed.x - end.x, seed.y - end.y, fill, |i, j| {
+                    f(end.x + i, end.y + j)
+                })
+                .map_err(SeedExtendError::Score)?;
+            steps.extend(set.global_alignment().steps().map(|step| step.mask()));
+            score += set.global_score();
+            steps.extend((0..seed.len).map(|_| StepMask::ALIGN));
+            score += seed.len as isize * strategy.match_score();
+            end = Cursor {
+                x: seed.x + seed.len,
+                y: seed.y + seed.len,
+            };
+        }
+
+        let tail = x_drop.extend(
+            x.len() - end.x,
+            y.len() - end.y,
+            Cursor { x: 0, y: 0 },
+            |i, j| f(end.x + i, end.y + j),
+        );
+        steps.extend(tail.steps().map(|step| step.mask()));
+        score += tail.score();
+
+        Ok(Some(Alignment::new(*head.origin(), steps, score)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pair::alignment_matrix::in_memory::AlignmentMatrix as InMemoryAlignmentMatrix;
+    use crate::pair::smith_waterman::SmithWaterman;
+
+    fn bytes(string: &str) -> Vec<u8> {
+        string.bytes().collect()
+    }
+
+    #[test]
+    fn seeds_are_merged() {
+        let x = bytes("XXACGTACGXX");
+        let index = KmerIndex::new(&x, 3).unwrap();
+        let seeds = index.seeds(&bytes("ACGTACG"));
+        assert!(seeds.contains(&Seed { x: 2, y: 0, len: 7 }));
+    }
+
+    #[test]
+    fn chain_works() {
+        let seeds = [
+            Seed { x: 0, y: 0, len: 4 },
+            Seed {
+                x: 2,
+                y: 10,
+                len: 3,
+            },
+            Seed { x: 6, y: 5, len: 5 },
+        ];
+        assert_eq!(chain(&seeds), vec![seeds[0], seeds[2]]);
+
+        // Seeds touching their predecessor in x or y can be chained.
+        let seeds = [
+            Seed { x: 4, y: 4, len: 2 },
+            Seed { x: 0, y: 0, len: 4 },
+            Seed { x: 6, y: 3, len: 3 },
+            Seed { x: 6, y: 6, len: 1 },
+        ];
+        assert_eq!(chain(&seeds), vec![seeds[1], seeds[0], seeds[3]]);
+    }
+
+    #[test]
+    fn seed_extend_works() {
+        let x = bytes("TTTTTTTTTTGATTACAGATTACAGATTACAXGATTACAGATTACATTTTTTTTTT");
+        let y = bytes("CCCGATTACAGATTACAGATTACAGATTACAGATTACACCC");
+        let strategy = SmithWaterman::new(2, -1, -2, -2);
+        let seed_extend: SeedExtend<InMemoryAlignmentMatrix, _> =
+            SeedExtend::new(strategy, 5, 4, 5).unwrap();
+        let alignment = seed_extend.align(&x, &y).unwrap().unwrap();
+        assert_eq!(alignment.origin(), &Cursor { x: 10, y: 3 });
+        assert_eq!(alignment.end(), Cursor { x: 46, y: 38 });
+        assert_eq!(alignment.score(), 35 * 2 - 2);
+    }
+
+    #[test]
+    fn unrelated_sequences_have_no_alignment() {
+        let strategy = SmithWaterman::new(2, -1, -2, -2);
+        let seed_extend: SeedExtend<InMemoryAlignmentMatrix, _> =
+            SeedExtend::new(strategy, 4, 4, 5).unwrap();
+        let alignment = seed_extend.align(&bytes("AAAAAAAA"), &bytes("CCCCCCCC"));
+        assert!(alignment.unwrap().is_none());
+    }
+
+    #[test]
+    fn seeds_off_the_band_are_not_bridged() {
+        // Bridging the two blocks would take a deletion of 30 elements, far
+        // outside the band, so only the longer block gets aligned.
+        let x = bytes(&format!("GATTACAGATTACA{}CCGGAACCTT", "T".repeat(30)));
+        let y = bytes("GATTACAGATTACACCGGAACCTT");
+        let strategy = SmithWaterman::new(2, -1, -2, -2);
+        let seed_extend: SeedExtend<InMemoryAlignmentMatrix, _> =
+            SeedExtend::new(strategy, 5, 4, 5).unwrap();
+        let alignment = seed_extend.align(&x, &y).unwrap().unwrap();
+        assert_eq!(alignment.origin(), &Cursor { x: 0, y: 0 });
+        assert_eq!(alignment.end(), Cursor { x: 14, y: 14 });
+        assert_eq!(alignment.score(), 28);
+    }
+}
@@ -9,6 +9,7 @@ pub struct SmithWaterman {
     align: isize,
     insert: isize,
     delete: isize,
+    bandwidth: Option<usize>,
 }
 
 impl SmithWaterman {
@@ -18,8 +19,14 @@ impl SmithWaterman {
             align,
             insert,
             delete,
+            bandwidth: None,
         }
     }
+
+    pub fn with_bandwidth(mut self, bandwidth: usize) -> SmithWaterman {
+        self.bandwidth = Some(bandwidth);
+        self
+    }
 }
 
 impl Strategy for SmithWaterman {
@@ -47,9 +54,13 @@ impl Strategy for SmithWaterman {
         }
     }
 
+    fn bandwidth(&self) -> Option<usize> {
+        self.bandwidth
+    }
+
     fn step_mask(&self, align: isize, insert: isize, delete: isize) -> StepMask {
         if cmp::max(cmp::max(align, insert), delete) > 0 {
-            StepMask::from_scores(align, insert, delete)
+            StepMask::from_scores(align, delete, insert)
         } else {
             StepMask::STOP
         }
@@ -0,0 +1,65 @@
+use crate::pair::strategy::Strategy;
+
+// Marks soft-masked (e.g. low-complexity or repeat) positions of x and y.
+// Matches involving a masked position score `masked_match` instead of the
+// match score of the strategy, so repeats don't dominate local alignments.
+#[derive(Clone, Debug)]
+pub struct SoftMask {
+    x: Vec<bool>,
+    y: Vec<bool>,
+    pub masked_match: isize,
+}
+
+impl SoftMask {
+    pub fn new(x: Vec<bool>, y: Vec<bool>, masked_match: isize) -> SoftMask {
+        SoftMask { x, y, masked_match }
+    }
+
+    // Lowercase letters are masked, as in soft-masked genome assemblies.
+    pub fn from_lowercase(x: &[u8], y: &[u8], masked_match: isize) -> SoftMask {
+        let masked = |sequence: &[u8]| sequence.iter().map(u8::is_ascii_lowercase).collect();
+        SoftMask::new(masked(x), masked(y), masked_match)
+    }
+
+    pub fn is_masked(&self, x: usize, y: usize) -> bool {
+        self.x[x] || self.y[y]
+    }
+
+    // Matches touching a masked position score `masked_match`, all else the
+    // strategy's score. With the lowercase convention `f` should ignore case.
+    pub fn scores<'a, S, F>(&'a self, strategy: &'a S, f: F) -> impl Fn(usize, usize) -> isize + 'a
+    where
+        S: Strategy,
+        F: Fn(usize, usize) -> bool + 'a,
+    {
+        move |i, j| match (f(i, j), self.is_masked(i, j)) {
+            (true, true) => self.masked_match,
+            (true, false) => strategy.match_score(),
+            (false, _) => strategy.mismatch_score(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pair::alignment_matrix::in_memory::AlignmentMatrix as InMemoryAlignmentMatrix;
+    use crate::pair::alignment_set::AlignmentSet;
+    use crate::pair::cursor::Cursor;
+    use crate::pair::smith_waterman::SmithWaterman;
+
+    #[test]
+    fn masked_matches_are_down_weighted() {
+        let x = b"acacacacacXXGATTACA";
+        let y = b"ACACACACACYYGATTACA";
+        let f = |i: usize, j: usize| x[i].eq_ignore_ascii_case(&y[j]);
+        let strategy = SmithWaterman::new(2, -3, -3, -3);
+        let mask = SoftMask::from_lowercase(x, y, 0);
+        let set: AlignmentSet<InMemoryAlignmentMatrix> =
+            AlignmentSet::builder(x.len(), y.len(), strategy.clone())
+                .build_with_scores(mask.scores(&strategy, f))
+                .unwrap();
+        assert_eq!(set.local_score(), 14);
+        assert_eq!(set.local_alignment().origin(), &Cursor { x: 12, y: 12 });
+    }
+}
@@ -6,6 +6,7 @@ pub enum Step {
     Align { x: usize, y: usize },
     Delete { x: usize },
     Insert { y: usize },
+    Transpose { x: usize, y: usize },
 }
 
 impl Step {
@@ -14,6 +15,7 @@ impl Step {
             Step::Align { x: _, y: _ } => StepMask::ALIGN,
             Step::Delete { x: _ } => StepMask::DELETE,
             Step::Insert { y: _ } => StepMask::INSERT,
+            Step::Transpose { x: _, y: _ } => StepMask::TRANSPOSE,
         }
     }
 
@@ -25,6 +27,10 @@ impl Step {
             },
             Step::Delete { x } => Run::Delete { x: (x)..(x + 1) },
             Step::Insert { y } => Run::Insert { y: (y)..(y + 1) },
+            Step::Transpose { x, y } => Run::Transpose {
+                x: (x)..(x + 2),
+                y: (y)..(y + 2),
+            },
         }
     }
 }
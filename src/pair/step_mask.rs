@@ -9,6 +9,11 @@ bitflags! {
         const ALIGN  = 0b00000010;
         const DELETE = 0b00000100;
         const INSERT = 0b00001000;
+        // Swaps two adjacent elements, consuming two of each sequence.
+        const TRANSPOSE = 0b00010000;
+        // Predecessor states of two-piece affine alignments, never part of a path.
+        const LONG_DELETE = 0b00100000;
+        const LONG_INSERT = 0b01000000;
     }
 }
 
@@ -10,7 +10,7 @@ pub struct Steps<'a> {
 }
 
 impl<'a> Steps<'a> {
-    pub fn new(inner: Iter<'a, StepMask>, cursor: Cursor) -> Steps {
+    pub fn new(inner: Iter<'a, StepMask>, cursor: Cursor) -> Steps<'a> {
         Steps { inner, cursor }
     }
 }
@@ -29,6 +29,10 @@ impl<'a> Iterator for Steps<'a> {
                 }),
                 StepMask::DELETE => Some(Step::Delete { x: cursor.x }),
                 StepMask::INSERT => Some(Step::Insert { y: cursor.y }),
+                StepMask::TRANSPOSE => Some(Step::Transpose {
+                    x: cursor.x,
+                    y: cursor.y,
+                }),
                 _ => None,
             }
         })
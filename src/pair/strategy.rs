@@ -1,4 +1,6 @@
-use crate::pair::StepMask;
+use crate::pair::{AffineGaps, Boundary, FreeEnds, StepMask};
+
+pub mod presets;
 
 pub trait Strategy: Clone {
     fn match_score(&self) -> isize;
@@ -7,4 +9,114 @@ pub trait Strategy: Clone {
     fn delete_score(&self) -> isize;
     fn total_score(&self, strategy: isize) -> isize;
     fn step_mask(&self, align: isize, insert: isize, delete: isize) -> StepMask;
+
+    // Score of deleting x[x] after y[..y] got aligned, respectively of
+    // inserting y[y] after x[..x]. Position-specific gaps are only supported
+    // by `AlignmentSet`.
+    fn delete_score_at(&self, _x: usize, _y: usize) -> isize {
+        self.delete_score()
+    }
+
+    fn insert_score_at(&self, _x: usize, _y: usize) -> isize {
+        self.insert_score()
+    }
+
+    // Like the above, for gaps at the start or end of either sequence, i.e.
+    // along the first and last row and column of the matrix.
+    fn terminal_delete_score_at(&self, x: usize, y: usize) -> isize {
+        self.delete_score_at(x, y)
+    }
+
+    fn terminal_insert_score_at(&self, x: usize, y: usize) -> isize {
+        self.insert_score_at(x, y)
+    }
+
+    // Only cells within `bandwidth` of the main diagonal get computed.
+    fn bandwidth(&self) -> Option<usize> {
+        None
+    }
+
+    fn free_ends(&self) -> FreeEnds {
+        FreeEnds::empty()
+    }
+
+    // How the first row (leading gaps in x) and the first column (leading
+    // gaps in y) of the matrix get initialized. Only supported by
+    // `AlignmentSet`.
+    fn first_row(&self) -> Boundary {
+        Boundary::of(self.free_ends(), FreeEnds::LEADING_X)
+    }
+
+    fn first_column(&self) -> Boundary {
+        Boundary::of(self.free_ends(), FreeEnds::LEADING_Y)
+    }
+
+    // x is a circular sequence which may be aligned starting at any of its
+    // rotations. Only supported by `AlignmentSet`.
+    fn circular(&self) -> bool {
+        false
+    }
+}
+
+// Gaps are scored by the affine model `gaps()`.
+pub trait AffineStrategy: Clone {
+    type Gaps: AffineGapModel;
+
+    fn match_score(&self) -> isize;
+    fn mismatch_score(&self) -> isize;
+    fn gaps(&self) -> &Self::Gaps;
+
+    // Models of deletions and insertions, which may differ. Only used by
+    // `AffineAlignmentSet`.
+    fn delete_gaps(&self) -> &Self::Gaps {
+        self.gaps()
+    }
+
+    fn insert_gaps(&self) -> &Self::Gaps {
+        self.gaps()
+    }
+}
+
+// Gaps are scored by the two-piece model `gaps()`.
+pub trait TwoPieceStrategy: Clone {
+    type Gaps: TwoPieceGapModel;
+
+    fn match_score(&self) -> isize;
+    fn mismatch_score(&self) -> isize;
+    fn gaps(&self) -> &Self::Gaps;
+}
+
+// A gap of length `n` scores `gap_score(n)`, for an arbitrary function of the length.
+pub trait GapStrategy: Clone {
+    fn match_score(&self) -> isize;
+    fn mismatch_score(&self) -> isize;
+    fn gap_score(&self, len: usize) -> isize;
+}
+
+// The score of a gap by its length. Closures `Fn(usize) -> isize` are gap
+// models too.
+pub trait GapModel: Clone {
+    fn gap_score(&self, len: usize) -> isize;
+}
+
+impl<F> GapModel for F
+where
+    F: Fn(usize) -> isize + Clone,
+{
+    fn gap_score(&self, len: usize) -> isize {
+        self(len)
+    }
+}
+
+// A gap of length `n` scores `open_score() + n * extend_score()`, which
+// `AffineAlignmentSet` needs to keep a single state per gap direction.
+pub trait AffineGapModel: GapModel {
+    fn open_score(&self) -> isize;
+    fn extend_score(&self) -> isize;
+}
+
+// A gap scores the better of two affine pieces, each of which gets a state
+// of its own in `TwoPieceAlignmentSet`.
+pub trait TwoPieceGapModel: GapModel {
+    fn pieces(&self) -> [AffineGaps; 2];
 }
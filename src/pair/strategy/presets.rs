@@ -0,0 +1,94 @@
+use crate::pair::free_ends::FreeEnds;
+use crate::pair::gotoh::Gotoh;
+use crate::pair::lcs::Lcs;
+use crate::pair::needleman_wunsch::NeedlemanWunsch;
+use crate::scoring::SubstitutionMatrix;
+
+// There is no `dtw_constrained(r)` preset, as this crate has no DTW strategy
+// to constrain.
+
+// The global score is the negated Levenshtein distance.
+pub fn edit_distance() -> NeedlemanWunsch {
+    NeedlemanWunsch::new(0, -1, -1, -1)
+}
+
+// The global score is the length of the longest common subsequence.
+pub fn lcs() -> Lcs {
+    Lcs::new()
+}
+
+// Aligns all of y (the query) against any part of x (the reference), as
+// leading and trailing gaps in x are free.
+pub fn semi_global(equal: isize, align: isize, insert: isize, delete: isize) -> NeedlemanWunsch {
+    NeedlemanWunsch::new(equal, align, insert, delete)
+        .with_free_ends(FreeEnds::LEADING_X | FreeEnds::TRAILING_X)
+}
+
+// Aligns all of x (the query) against any part of y (the reference), which
+// `Alignment::y_range` of the global alignment returns.
+pub fn fitting(equal: isize, align: isize, insert: isize, delete: isize) -> NeedlemanWunsch {
+    NeedlemanWunsch::new(equal, align, insert, delete)
+        .with_free_ends(FreeEnds::LEADING_Y | FreeEnds::TRAILING_Y)
+}
+
+// Aligns a suffix of x against a prefix of y.
+pub fn overlap(equal: isize, align: isize, insert: isize, delete: isize) -> NeedlemanWunsch {
+    NeedlemanWunsch::new(equal, align, insert, delete)
+        .with_free_ends(FreeEnds::LEADING_X | FreeEnds::TRAILING_Y)
+}
+
+// The default nucleotide scores of BLASTN: +2/-3, gaps of length `n` cost
+// `5 + 2 * n`. Meant for local alignments.
+pub fn blastn_like() -> Gotoh {
+    Gotoh::new(2, -3, -5, -2)
+}
+
+// The default protein scores of BLASTP: BLOSUM62, gaps of length `n` cost
+// `11 + n`. The matrix provides the substitution scores, e.g. via
+// `AffineAlignmentSet::with_scores(.., matrix.scores(x, y))`.
+pub fn protein_blosum62_affine() -> (Gotoh, SubstitutionMatrix) {
+    (Gotoh::new(0, 0, -11, -1), SubstitutionMatrix::blosum62())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distance::levenshtein;
+    use crate::pair::affine_alignment_set::AffineAlignmentSet;
+    use crate::pair::alignment_matrix::in_memory::AlignmentMatrix as InMemoryAlignmentMatrix;
+    use crate::pair::alignment_set::AlignmentSet;
+    use crate::pair::cursor::Cursor;
+
+    #[test]
+    fn presets_work() {
+        let x = b"kitten";
+        let y = b"sitting";
+        let set: AlignmentSet<InMemoryAlignmentMatrix> =
+            AlignmentSet::new(x.len(), y.len(), edit_distance(), |i, j| x[i] == y[j]).unwrap();
+        assert_eq!(-set.global_score() as usize, levenshtein(x, y));
+        let set: AlignmentSet<InMemoryAlignmentMatrix> =
+            AlignmentSet::new(x.len(), y.len(), lcs(), |i, j| x[i] == y[j]).unwrap();
+        assert_eq!(set.global_score(), 4);
+
+        let x = b"MKVLAAGIVLLLSAQAWA";
+        let y = b"MKVLAWGIVLLSAQ";
+        let (strategy, matrix) = protein_blosum62_affine();
+        let set: AffineAlignmentSet<InMemoryAlignmentMatrix> =
+            AffineAlignmentSet::with_scores(x.len(), y.len(), strategy, matrix.scores(x, y))
+                .unwrap();
+        // MKVLA A GIVLL L SAQ
+        // MKVLA W GIVLL - SAQ
+        assert_eq!(set.local_score(), 22 - 3 + 22 - 12 + 13);
+        assert_eq!(set.local_alignment().origin(), &Cursor { x: 0, y: 0 });
+
+        let x = b"ACGTACGTAC";
+        let y = b"ACGTCGTAC";
+        let set: AffineAlignmentSet<InMemoryAlignmentMatrix> =
+            AffineAlignmentSet::new(x.len(), y.len(), blastn_like(), |i, j| x[i] == y[j]).unwrap();
+        // ACGT A CGTAC
+        // ACGT - CGTAC
+        // Nine matches and a gap of length one, costing 5 + 2 * 1.
+        assert_eq!(set.local_score(), 9 * 2 - 7);
+        assert_eq!(set.global_score(), 9 * 2 - 7);
+    }
+}
@@ -0,0 +1,112 @@
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+use crate::pair::alignment::Alignment;
+use crate::pair::alignment_set::MIN_SCORE;
+use crate::pair::cursor::Cursor;
+use crate::pair::step_mask::StepMask;
+
+const STEPS: [StepMask; 3] = [StepMask::ALIGN, StepMask::DELETE, StepMask::INSERT];
+
+// What `AlignmentSet::k_best` needs beyond the cell scores: the score of
+// the align, delete and insert step into every cell, `MIN_SCORE` where
+// there is no such step, and the cells global alignments may end in.
+pub(crate) struct StepScores {
+    pub(crate) steps: Vec<[isize; 3]>,
+    pub(crate) ends: Vec<Cursor>,
+}
+
+// A partial path from an end cell back to `cursor`. Its `bound` is the
+// score of the best alignment that completes it.
+struct Candidate {
+    bound: isize,
+    suffix: isize,
+    cursor: Cursor,
+    path: Option<usize>,
+    order: usize,
+}
+
+impl PartialEq for Candidate {
+    fn eq(&self, other: &Candidate) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Candidate) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Candidate) -> Ordering {
+        self.bound
+            .cmp(&other.bound)
+            .then_with(|| other.order.cmp(&self.order))
+    }
+}
+
+impl StepScores {
+    // Best-first search backwards from the ends. As the cell scores bound
+    // the prefixes exactly, complete paths are found in order of their
+    // score. A path is complete in a cell without steps into it that is not
+    // blocked, i.e. the origin or a free leading end.
+    pub(crate) fn k_best(&self, scores: &[isize], width: usize, k: usize) -> Vec<Alignment> {
+        let index = |cursor: &Cursor| cursor.y * width + cursor.x;
+        let mut alignments = vec![];
+        let mut paths: Vec<(StepMask, Option<usize>)> = vec![];
+        let mut heap: BinaryHeap<Candidate> = self
+            .ends
+            .iter()
+            .enumerate()
+            .map(|(order, cursor)| Candidate {
+                bound: scores[index(cursor)],
+                suffix: 0,
+                cursor: *cursor,
+                path: None,
+                order,
+            })
+            .collect();
+        let mut order = heap.len();
+        while alignments.len() < k {
+            let candidate = match heap.pop() {
+                Some(candidate) => candidate,
+                None => break,
+            };
+            let cell = self.steps[index(&candidate.cursor)];
+            if cell.iter().all(|&score| score == MIN_SCORE) {
+                if scores[index(&candidate.cursor)] > MIN_SCORE {
+                    let mut steps = vec![];
+                    let mut path = candidate.path;
+                    while let Some(i) = path {
+                        steps.push(paths[i].0);
+                        path = paths[i].1;
+                    }
+                    let score = candidate.suffix + scores[index(&candidate.cursor)];
+                    alignments.push(Alignment::new(candidate.cursor, steps, score));
+                }
+                continue;
+            }
+            for (&step, &score) in STEPS.iter().zip(cell.iter()) {
+                if score == MIN_SCORE {
+                    continue;
+                }
+                let mut cursor = candidate.cursor;
+                cursor.apply_backwards_step(step);
+                paths.push((step, candidate.path));
+                let suffix = candidate.suffix.saturating_add(score);
+                heap.push(Candidate {
+                    bound: suffix.saturating_add(scores[index(&cursor)]),
+                    suffix,
+                    cursor,
+                    path: Some(paths.len() - 1),
+                    order,
+                });
+                order += 1;
+            }
+        }
+        alignments
+    }
+}
@@ -0,0 +1,57 @@
+use crate::pair::gap_models::{AffineGaps, TwoPieceGaps};
+use crate::pair::strategy::{TwoPieceGapModel, TwoPieceStrategy};
+
+#[derive(Clone, Debug)]
+pub struct TwoPiece<G = TwoPieceGaps> {
+    pub equal: isize,
+    pub align: isize,
+    pub gaps: G,
+}
+
+impl TwoPiece {
+    pub fn new(
+        equal: isize,
+        align: isize,
+        open: isize,
+        extend: isize,
+        long_open: isize,
+        long_extend: isize,
+    ) -> TwoPiece {
+        let gaps = TwoPieceGaps {
+            short: AffineGaps { open, extend },
+            long: AffineGaps {
+                open: long_open,
+                extend: long_extend,
+            },
+        };
+        TwoPiece::from_gaps(equal, align, gaps)
+    }
+}
+
+impl<G> TwoPiece<G>
+where
+    G: TwoPieceGapModel,
+{
+    pub fn from_gaps(equal: isize, align: isize, gaps: G) -> TwoPiece<G> {
+        TwoPiece { equal, align, gaps }
+    }
+}
+
+impl<G> TwoPieceStrategy for TwoPiece<G>
+where
+    G: TwoPieceGapModel,
+{
+    type Gaps = G;
+
+    fn match_score(&self) -> isize {
+        self.equal
+    }
+
+    fn mismatch_score(&self) -> isize {
+        self.align
+    }
+
+    fn gaps(&self) -> &G {
+        &self.gaps
+    }
+}
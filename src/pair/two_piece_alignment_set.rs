@@ -0,0 +1,377 @@
+use crate::pair::affine_alignment_set::gap_score;
+use crate::pair::alignment::Alignment;
+use crate::pair::alignment_matrix::AlignmentMatrix;
+use crate::pair::alignment_set::MIN_SCORE;
+use crate::pair::alignments::Preference;
+use crate::pair::cursor::Cursor;
+use crate::pair::step_mask::StepMask;
+use crate::pair::strategy::{AffineGapModel, TwoPieceGapModel, TwoPieceStrategy};
+
+// The DP states: ending in an alignment, a short deletion or insertion, or a
+// long deletion or insertion. Of a short and long gap state sharing a
+// score, the short one is preferred.
+const STATES: [StepMask; 5] = [
+    StepMask::ALIGN,
+    StepMask::DELETE,
+    StepMask::INSERT,
+    StepMask::LONG_DELETE,
+    StepMask::LONG_INSERT,
+];
+
+const ALIGN: usize = 0;
+const DELETE: usize = 1;
+const INSERT: usize = 2;
+const LONG_DELETE: usize = 3;
+const LONG_INSERT: usize = 4;
+
+#[derive(Copy, Clone)]
+struct Scores([isize; 5]);
+
+impl Scores {
+    fn empty() -> Scores {
+        Scores([MIN_SCORE; 5])
+    }
+
+    fn max(&self) -> isize {
+        self.0.iter().cloned().max().unwrap()
+    }
+
+    fn mask(&self) -> StepMask {
+        let max = self.max();
+        let mut mask = StepMask::empty();
+        for (state, &score) in STATES.iter().zip(self.0.iter()) {
+            if score == max {
+                mask.insert(*state);
+            }
+        }
+        mask
+    }
+}
+
+// The step of the alignment path leaving a state.
+fn step(state: StepMask) -> StepMask {
+    match state {
+        StepMask::LONG_DELETE => StepMask::DELETE,
+        StepMask::LONG_INSERT => StepMask::INSERT,
+        state => state,
+    }
+}
+
+#[derive(Copy, Clone)]
+struct Highscore {
+    score: isize,
+    // The states reaching the score.
+    states: StepMask,
+    cursor: Cursor,
+}
+
+struct Highscores {
+    local: Highscore,
+    global: Highscore,
+}
+
+impl Highscores {
+    fn update(&mut self, highscore: Highscore) {
+        if highscore.score >= self.local.score {
+            self.local = highscore
+        }
+    }
+}
+
+// Affine alignment with a second, usually cheaper to extend, gap state per
+// direction, so that very long gaps are not broken up into mismatches. As in
+// `AffineAlignmentSet` each state has its own matrix of predecessor states.
+pub struct TwoPieceAlignmentSet<T> {
+    matrices: Vec<T>,
+    highscores: Highscores,
+    preference: Preference,
+}
+
+impl<T, E> TwoPieceAlignmentSet<T>
+where
+    T: AlignmentMatrix<Error = E>,
+{
+    pub fn new<S: TwoPieceStrategy, F>(
+        x_len: usize,
+        y_len: usize,
+        strategy: S,
+        f: F,
+    ) -> Result<TwoPieceAlignmentSet<T>, E>
+    where
+        F: Fn(usize, usize) -> bool,
+    {
+        let width = x_len + 1;
+        let height = y_len + 1;
+
+        let mut matrices = vec![];
+        for _ in STATES.iter() {
+            matrices.push(T::new(width, height)?);
+        }
+        let gaps = strategy
+            .gaps()
+            .pieces()
+            .map(|piece| (piece.open_score(), piece.extend_score()));
+
+        Self::prepare_matrices(&mut matrices);
+        let mut row: Vec<Scores> = (0..width)
+            .map(|x| {
+                let mut scores = Scores::empty();
+                if x == 0 {
+                    scores.0[ALIGN] = 0;
+                } else {
+                    scores.0[DELETE] = gap_score(gaps[0].0, gaps[0].1, x);
+                    scores.0[LONG_DELETE] = gap_score(gaps[1].0, gaps[1].1, x);
+                }
+                scores
+            })
+            .collect();
+        let mut highscores = Highscores {
+            local: Highscore {
+                score: 0,
+                states: StepMask::ALIGN,
+                cursor: Cursor { x: 0, y: 0 },
+            },
+            global: Highscore {
+                score: 0,
+                states: StepMask::ALIGN,
+                cursor: Cursor { x: 0, y: 0 },
+            },
+        };
+
+        for y in 0..y_len {
+            let mut last_diagonal = row[0];
+            let mut first = Scores::empty();
+            first.0[INSERT] = gap_score(gaps[0].0, gaps[0].1, y + 1);
+            first.0[LONG_INSERT] = gap_score(gaps[1].0, gaps[1].1, y + 1);
+            row[0] = first;
+            for x in 0..x_len {
+                let previous = (last_diagonal, row[x], row[x + 1]);
+                let equal = f(x, y);
+                let (masks, scores) = Self::calculate_cell(&strategy, &gaps, previous, equal);
+                let cursor = Cursor { x: x + 1, y: y + 1 };
+                highscores.update(Highscore {
+                    score: scores.max(),
+                    states: scores.mask(),
+                    cursor,
+                });
+                for (matrix, mask) in matrices.iter_mut().zip(masks.iter()) {
+                    matrix.set_at(&cursor, *mask);
+                }
+                last_diagonal = row[x + 1];
+                row[x + 1] = scores;
+            }
+        }
+
+        highscores.global = Highscore {
+            score: row[x_len].max(),
+            states: row[x_len].mask(),
+            cursor: Cursor { x: x_len, y: y_len },
+        };
+
+        Ok(TwoPieceAlignmentSet {
+            matrices,
+            highscores,
+            preference: Preference::default(),
+        })
+    }
+
+    fn calculate_cell<S: TwoPieceStrategy>(
+        strategy: &S,
+        gaps: &[(isize, isize); 2],
+        previous_scores: (Scores, Scores, Scores),
+        equal: bool,
+    ) -> ([StepMask; 5], Scores) {
+        let (diagonal, left, up) = previous_scores;
+        let substitution = if equal {
+            strategy.match_score()
+        } else {
+            strategy.mismatch_score()
+        };
+        // Gap states either get extended or opened from any other state.
+        // Scores saturate, as states may be unreachable at `MIN_SCORE`.
+        let gap = |previous: &Scores, state: usize, (open, extend): (isize, isize)| {
+            let mut candidates = Scores::empty();
+            for (i, &score) in previous.0.iter().enumerate() {
+                candidates.0[i] = if i == state {
+                    score.saturating_add(extend)
+                } else {
+                    score.saturating_add(open.saturating_add(extend))
+                };
+            }
+            candidates
+        };
+        let candidates = [
+            diagonal,
+            gap(&left, DELETE, gaps[0]),
+            gap(&up, INSERT, gaps[0]),
+            gap(&left, LONG_DELETE, gaps[1]),
+            gap(&up, LONG_INSERT, gaps[1]),
+        ];
+        let mut masks = [StepMask::STOP; 5];
+        let mut scores = Scores::empty();
+        for (state, candidate) in candidates.iter().enumerate() {
+            masks[state] = candidate.mask();
+            scores.0[state] = candidate.max();
+        }
+        scores.0[ALIGN] = scores.0[ALIGN].saturating_add(substitution);
+        (masks, scores)
+    }
+
+    // Gaps along the first row and column start from the origin, which
+    // is in the alignment state.
+    fn prepare_matrices(matrices: &mut [T]) {
+        let origin = Cursor { x: 0, y: 0 };
+        let (width, height) = (matrices[0].width(), matrices[0].height());
+        for matrix in matrices.iter_mut() {
+            matrix.set_at(&origin, StepMask::STOP);
+        }
+        for (state, matrix) in matrices.iter_mut().enumerate() {
+            for y in 1..height {
+                let mask = match state {
+                    INSERT | LONG_INSERT if y == 1 => StepMask::ALIGN,
+                    INSERT | LONG_INSERT => STATES[state],
+                    _ => StepMask::STOP,
+                };
+                matrix.set_at(&Cursor { x: 0, y }, mask);
+            }
+            for x in 1..width {
+                let mask = match state {
+                    DELETE | LONG_DELETE if x == 1 => StepMask::ALIGN,
+                    DELETE | LONG_DELETE => STATES[state],
+                    _ => StepMask::STOP,
+                };
+                matrix.set_at(&Cursor { x, y: 0 }, mask);
+            }
+        }
+    }
+
+    fn matrix(&self, state: StepMask) -> &T {
+        let index = STATES.iter().position(|s| *s == state).unwrap();
+        &self.matrices[index]
+    }
+
+    // Of co-optimal states the one whose step comes first in `preference`
+    // gets followed, `Preference::GAPS_RIGHT` by default.
+    pub fn with_preference(mut self, preference: Preference) -> TwoPieceAlignmentSet<T> {
+        self.preference = preference;
+        self
+    }
+
+    fn preferred(&self, states: StepMask) -> Option<StepMask> {
+        let steps = STATES
+            .iter()
+            .filter(|state| states.contains(**state))
+            .fold(StepMask::empty(), |steps, state| steps | step(*state));
+        let preferred = self.preference.first(steps)?;
+        STATES
+            .iter()
+            .copied()
+            .find(|state| states.contains(*state) && step(*state) == preferred)
+    }
+
+    fn alignment(&self, highscore: &Highscore) -> Alignment {
+        let zero = Cursor { x: 0, y: 0 };
+        let mut cursor = highscore.cursor;
+        let mut state = self.preferred(highscore.states);
+        let mut steps = vec![];
+        while let Some(current) = state {
+            if cursor == zero {
+                break;
+            }
+            let mask = self.matrix(current).at(&cursor);
+            if mask == StepMask::STOP {
+                break;
+            }
+            steps.push(step(current));
+            cursor.apply_backwards_step(step(current));
+            state = self.preferred(mask);
+        }
+        steps.reverse();
+        Alignment::new(cursor, steps, highscore.score)
+    }
+
+    pub fn local_score(&self) -> isize {
+        self.highscores.local.score
+    }
+
+    pub fn global_score(&self) -> isize {
+        self.highscores.global.score
+    }
+
+    pub fn local_max(&self) -> &Cursor {
+        &self.highscores.local.cursor
+    }
+
+    pub fn global_max(&self) -> &Cursor {
+        &self.highscores.global.cursor
+    }
+
+    pub fn local_alignment(&self) -> Alignment {
+        self.alignment(&self.highscores.local)
+    }
+
+    pub fn global_alignment(&self) -> Alignment {
+        self.alignment(&self.highscores.global)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pair::affine_alignment_set::AffineAlignmentSet;
+    use crate::pair::alignment_matrix::in_memory::AlignmentMatrix as InMemoryAlignmentMatrix;
+    use crate::pair::gotoh::Gotoh;
+    use crate::pair::two_piece::TwoPiece;
+
+    fn chars(string: &str) -> Vec<char> {
+        string.chars().collect()
+    }
+
+    #[test]
+    fn long_gaps_are_cheap() {
+        let x = chars("ACGTACGTAAAAAAAAAAAAAAAAAAAAACGTACGT");
+        let y = chars("ACGTACGTCGTACGT");
+        let strategy = TwoPiece::new(2, -1, -4, -2, -20, 0);
+        let set: TwoPieceAlignmentSet<InMemoryAlignmentMatrix> =
+            TwoPieceAlignmentSet::new(x.len(), y.len(), strategy, |i, j| x[i] == y[j]).unwrap();
+        assert_eq!(set.global_score(), 15 * 2 - 20);
+        let alignment = set.global_alignment();
+        let gaps = alignment.runs().filter(|run| run.mask() != StepMask::ALIGN);
+        assert_eq!(gaps.count(), 1);
+        assert_eq!(alignment.end(), Cursor { x: 36, y: 15 });
+    }
+
+    #[test]
+    fn short_gaps_match_gotoh() {
+        let x = chars("The quick brown fox jumps over the lazy dog.");
+        let y = chars("The brown dog jumps over the very lazy snail.");
+        let two_piece: TwoPieceAlignmentSet<InMemoryAlignmentMatrix> = TwoPieceAlignmentSet::new(
+            x.len(),
+            y.len(),
+            TwoPiece::new(1, -1, -2, -1, -100, -1),
+            |i, j| x[i] == y[j],
+        )
+        .unwrap();
+        let affine: AffineAlignmentSet<InMemoryAlignmentMatrix> =
+            AffineAlignmentSet::new(x.len(), y.len(), Gotoh::new(1, -1, -2, -1), |i, j| {
+                x[i] == y[j]
+            })
+            .unwrap();
+        assert_eq!(two_piece.global_score(), affine.global_score());
+        assert_eq!(two_piece.local_score(), affine.local_score());
+    }
+
+    #[test]
+    fn huge_gap_scores_saturate() {
+        let x = chars("ACGT");
+        let gap = -(isize::MAX / 2);
+        let set: TwoPieceAlignmentSet<InMemoryAlignmentMatrix> = TwoPieceAlignmentSet::new(
+            x.len(),
+            x.len(),
+            TwoPiece::new(1, -1, gap, gap, gap, gap),
+            |i, j| x[i] == x[j],
+        )
+        .unwrap();
+        assert_eq!(set.global_score(), 4);
+    }
+}
@@ -0,0 +1,130 @@
+use std::marker::PhantomData;
+
+use crate::pair::alignment::Alignment;
+use crate::pair::alignment_matrix::AlignmentMatrix;
+use crate::pair::alignment_set::{AlignmentSet, ScoreError};
+use crate::pair::cursor::Cursor;
+use crate::pair::strategy::Strategy;
+
+// Yields non-intersecting local alignments by decreasing score. After each
+// alignment the cells on its path are blocked and the matrix is recomputed.
+pub struct WatermanEggert<T, S, F> {
+    x_len: usize,
+    y_len: usize,
+    strategy: S,
+    f: F,
+    cutoff: isize,
+    used: Vec<bool>,
+    done: bool,
+    matrix: PhantomData<T>,
+}
+
+impl<T, S, F> WatermanEggert<T, S, F>
+where
+    S: Strategy,
+    F: Fn(usize, usize) -> bool,
+{
+    // Stops before the first alignment scoring below `cutoff`.
+    pub fn new(
+        x_len: usize,
+        y_len: usize,
+        strategy: S,
+        cutoff: isize,
+        f: F,
+    ) -> WatermanEggert<T, S, F> {
+        WatermanEggert {
+            x_len,
+            y_len,
+            strategy,
+            f,
+            cutoff,
+            used: vec![false; (x_len + 1) * (y_len + 1)],
+            done: false,
+            matrix: PhantomData,
+        }
+    }
+}
+
+impl<T, S, F, E> Iterator for WatermanEggert<T, S, F>
+where
+    T: AlignmentMatrix<Error = E>,
+    S: Strategy,
+    F: Fn(usize, usize) -> bool,
+{
+    type Item = Result<Alignment, ScoreError<E>>;
+
+    fn next(&mut self) -> Option<Result<Alignment, ScoreError<E>>> {
+        if self.done {
+            return None;
+        }
+        let width = self.x_len + 1;
+        let used = &self.used;
+        let set: Result<AlignmentSet<T>, ScoreError<E>> =
+            AlignmentSet::builder(self.x_len, self.y_len, self.strategy.clone())
+                .with_blocked(|cursor| used[cursor.y * width + cursor.x])
+                .build(&self.f);
+        let set = match set {
+            Ok(set) => set,
+            Err(error) => {
+                self.done = true;
+                return Some(Err(error));
+            }
+        };
+        if set.local_score() <= 0 || set.local_score() < self.cutoff {
+            self.done = true;
+            return None;
+        }
+        let alignment = set.local_alignment();
+        let mut cursor: Cursor = *alignment.origin();
+        for step in alignment.steps() {
+            cursor.apply_forwards_step(step.mask());
+            self.used[cursor.y * width + cursor.x] = true;
+        }
+        Some(Ok(alignment))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pair::alignment_matrix::in_memory::AlignmentMatrix as InMemoryAlignmentMatrix;
+    use crate::pair::smith_waterman::SmithWaterman;
+
+    #[test]
+    fn repeats_are_found() {
+        let x: Vec<char> = "XXACGTACXXXXACGTACXX".chars().collect();
+        let y: Vec<char> = "YYYACGTACYYY".chars().collect();
+        let strategy = SmithWaterman::new(2, -1, -1, -1);
+        let alignments: Vec<Alignment> = WatermanEggert::<InMemoryAlignmentMatrix, _, _>::new(
+            x.len(),
+            y.len(),
+            strategy,
+            8,
+            |i, j| x[i] == y[j],
+        )
+        .map(Result::unwrap)
+        .collect();
+        assert_eq!(alignments.len(), 2);
+        let mut origins: Vec<Cursor> = alignments.iter().map(|a| *a.origin()).collect();
+        origins.sort_by_key(|cursor| cursor.x);
+        assert_eq!(origins, vec![Cursor { x: 2, y: 3 }, Cursor { x: 12, y: 3 }]);
+        assert!(alignments.iter().all(|a| a.score() == 12));
+    }
+
+    #[test]
+    fn cutoff_is_respected() {
+        let x: Vec<char> = "ACGTXXXXAC".chars().collect();
+        let y: Vec<char> = "ACGT".chars().collect();
+        let strategy = SmithWaterman::new(2, -1, -1, -1);
+        let scores: Vec<isize> = WatermanEggert::<InMemoryAlignmentMatrix, _, _>::new(
+            x.len(),
+            y.len(),
+            strategy,
+            4,
+            |i, j| x[i] == y[j],
+        )
+        .map(|alignment| alignment.unwrap().score())
+        .collect();
+        assert_eq!(scores, vec![8, 4]);
+    }
+}
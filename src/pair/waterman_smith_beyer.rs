@@ -0,0 +1,107 @@
+use crate::pair::alignment::Alignment;
+use crate::pair::alignments::Preference;
+use crate::pair::score_table;
+use crate::pair::step_mask::StepMask;
+use crate::pair::strategy::GapStrategy;
+
+// Global alignment with arbitrary gap scores. Every cell considers gaps of
+// all lengths, so this takes O(n * m * (n + m)) time.
+#[derive(Clone, Debug)]
+pub struct WatermanSmithBeyer<S> {
+    strategy: S,
+    preference: Preference,
+}
+
+impl<S> WatermanSmithBeyer<S>
+where
+    S: GapStrategy,
+{
+    pub fn new(strategy: S) -> WatermanSmithBeyer<S> {
+        WatermanSmithBeyer {
+            strategy,
+            preference: Preference::default(),
+        }
+    }
+
+    // Of co-optimal steps into a cell the one coming first in `preference`
+    // is taken.
+    pub fn with_preference(mut self, preference: Preference) -> WatermanSmithBeyer<S> {
+        self.preference = preference;
+        self
+    }
+
+    pub fn global_alignment<F>(&self, x_len: usize, y_len: usize, f: F) -> Alignment
+    where
+        F: Fn(usize, usize) -> bool,
+    {
+        let strategy = &self.strategy;
+        score_table::global_alignment(x_len, y_len, &self.preference, |x, y, candidate| {
+            if x > 0 && y > 0 {
+                let score = if f(x - 1, y - 1) {
+                    strategy.match_score()
+                } else {
+                    strategy.mismatch_score()
+                };
+                candidate(score, StepMask::ALIGN, 1);
+            }
+            for len in 1..=x {
+                candidate(strategy.gap_score(len), StepMask::DELETE, len);
+            }
+            for len in 1..=y {
+                candidate(strategy.gap_score(len), StepMask::INSERT, len);
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pair::affine_alignment_set::AffineAlignmentSet;
+    use crate::pair::alignment_matrix::in_memory::AlignmentMatrix as InMemoryAlignmentMatrix;
+    use crate::pair::convex::Convex;
+    use crate::pair::cursor::Cursor;
+    use crate::pair::gotoh::Gotoh;
+
+    fn chars(string: &str) -> Vec<char> {
+        string.chars().collect()
+    }
+
+    #[test]
+    fn affine_gaps_match_gotoh() {
+        let x = chars("The quick brown fox jumps over the lazy dog.");
+        let y = chars("The brown dog jumps over the very lazy snail.");
+        let strategy = Convex::new(2, -1, |len| -3 - len as isize);
+        let aligner = WatermanSmithBeyer::new(strategy);
+        let alignment = aligner.global_alignment(x.len(), y.len(), |i, j| x[i] == y[j]);
+        let set: AffineAlignmentSet<InMemoryAlignmentMatrix> =
+            AffineAlignmentSet::new(x.len(), y.len(), Gotoh::new(2, -1, -3, -1), |i, j| {
+                x[i] == y[j]
+            })
+            .unwrap();
+        assert_eq!(alignment.score(), set.global_score());
+        assert_eq!(
+            alignment.end(),
+            Cursor {
+                x: x.len(),
+                y: y.len()
+            }
+        );
+    }
+
+    #[test]
+    fn logarithmic_gaps_work() {
+        let x = chars("ACGTACGTACGTTTTTTTTTTTTACGT");
+        let y = chars("ACGTACGTACGTACGT");
+        let strategy = Convex::new(2, -2, |len| -4 - (2.0 * (len as f64).ln()) as isize);
+        let aligner = WatermanSmithBeyer::new(strategy);
+        let alignment = aligner.global_alignment(x.len(), y.len(), |i, j| x[i] == y[j]);
+        // A single long gap is cheaper than several short ones.
+        assert_eq!(alignment.score(), 16 * 2 - 4 - 4);
+        let gaps = alignment
+            .steps()
+            .filter(|step| step.mask() == StepMask::DELETE)
+            .count();
+        assert_eq!(gaps, 11);
+    }
+}
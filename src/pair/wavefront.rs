@@ -0,0 +1,396 @@
+use std::cmp;
+
+use crate::pair::alignment::Alignment;
+use crate::pair::cursor::Cursor;
+use crate::pair::step_mask::StepMask;
+use crate::pair::strategy::{AffineGapModel, AffineStrategy};
+
+const NONE: isize = isize::MIN / 2;
+
+fn valid(x: isize, k: isize, n: isize, m: isize) -> isize {
+    if x >= 0 && x <= n && x - k >= 0 && x - k <= m {
+        x
+    } else {
+        NONE
+    }
+}
+
+// Furthest reaching x offsets per diagonal `k = x - y` for a given penalty.
+struct Front {
+    lo: isize,
+    hi: isize,
+    align: Vec<isize>,
+    insert: Vec<isize>,
+    delete: Vec<isize>,
+}
+
+impl Front {
+    fn new(lo: isize, hi: isize) -> Front {
+        let len = (hi - lo + 1) as usize;
+        Front {
+            lo,
+            hi,
+            align: vec![NONE; len],
+            insert: vec![NONE; len],
+            delete: vec![NONE; len],
+        }
+    }
+
+    fn index(&self, k: isize) -> Option<usize> {
+        if k < self.lo || k > self.hi {
+            None
+        } else {
+            Some((k - self.lo) as usize)
+        }
+    }
+
+    fn get(&self, state: StepMask, k: isize) -> isize {
+        match self.index(k) {
+            Some(i) => match state {
+                StepMask::ALIGN => self.align[i],
+                StepMask::INSERT => self.insert[i],
+                _ => self.delete[i],
+            },
+            None => NONE,
+        }
+    }
+}
+
+// Penalties of a mismatch and of opening and extending deletions and
+// insertions, which may differ.
+#[derive(Copy, Clone)]
+struct Penalties {
+    mismatch: usize,
+    delete: (usize, usize),
+    insert: (usize, usize),
+}
+
+#[derive(Debug, PartialEq)]
+pub enum WavefrontError {
+    // The match score is not zero.
+    MatchScore { score: isize },
+    // The mismatch score is not negative.
+    MismatchScore { score: isize },
+    // The gap open score is positive.
+    GapOpenScore { score: isize },
+    // The gap extend score is not negative.
+    GapExtendScore { score: isize },
+}
+
+// Gap-affine alignment by wavefronts over penalty scores: the negated
+// mismatch, gap open and gap extend scores of the strategy are used as
+// penalties, which requires a match score of zero. Deletions and insertions
+// are penalized by `delete_gaps` and `insert_gaps` respectively. The
+// backtrace always extends matches as far as possible, then prefers
+// substitutions over deletions over insertions; it takes no `Preference`,
+// as furthest-reaching points only keep one of co-optimal paths.
+#[derive(Clone, Debug)]
+pub struct Wavefront<S> {
+    strategy: S,
+}
+
+impl<S> Wavefront<S>
+where
+    S: AffineStrategy,
+{
+    pub fn new(strategy: S) -> Result<Wavefront<S>, WavefrontError> {
+        let score = strategy.match_score();
+        if score != 0 {
+            return Err(WavefrontError::MatchScore { score });
+        }
+        let score = strategy.mismatch_score();
+        if score >= 0 {
+            return Err(WavefrontError::MismatchScore { score });
+        }
+        for gaps in [strategy.delete_gaps(), strategy.insert_gaps()] {
+            let score = gaps.open_score();
+            if score > 0 {
+                return Err(WavefrontError::GapOpenScore { score });
+            }
+            let score = gaps.extend_score();
+            if score >= 0 {
+                return Err(WavefrontError::GapExtendScore { score });
+            }
+        }
+        Ok(Wavefront { strategy })
+    }
+
+    fn penalties(&self) -> Penalties {
+        let gap = |gaps: &S::Gaps| (-gaps.open_score() as usize, -gaps.extend_score() as usize);
+        Penalties {
+            mismatch: -self.strategy.mismatch_score() as usize,
+            delete: gap(self.strategy.delete_gaps()),
+            insert: gap(self.strategy.insert_gaps()),
+        }
+    }
+
+    pub fn global_alignment<F>(&self, x_len: usize, y_len: usize, f: F) -> Alignment
+    where
+        F: Fn(usize, usize) -> bool,
+    {
+        let penalties = self.penalties();
+        let (n, m) = (x_len as isize, y_len as isize);
+        let k_end = n - m;
+        let extend_matches = |wavefront: &mut Front| {
+            for k in wavefront.lo..=wavefront.hi {
+                let i = (k - wavefront.lo) as usize;
+                let mut x = wavefront.align[i];
+                if x < 0 {
+                    continue;
+                }
+                while x < n && x - k < m && f(x as usize, (x - k) as usize) {
+                    x += 1;
+                }
+                wavefront.align[i] = x;
+            }
+        };
+        let mut wavefronts: Vec<Option<Front>> = vec![];
+        let mut score = 0;
+        loop {
+            let mut wavefront = if score == 0 {
+                let mut wavefront = Front::new(0, 0);
+                wavefront.align[0] = 0;
+                Some(wavefront)
+            } else {
+                let source = |penalty: usize| {
+                    if score >= penalty {
+                        wavefronts[score - penalty].as_ref()
+                    } else {
+                        None
+                    }
+                };
+                Self::next_wavefront(&source, penalties, n, m)
+            };
+            if let Some(wavefront) = wavefront.as_mut() {
+                extend_matches(wavefront);
+            }
+            let done = wavefront
+                .as_ref()
+                .is_some_and(|wavefront| wavefront.get(StepMask::ALIGN, k_end) >= n);
+            wavefronts.push(wavefront);
+            if done {
+                break;
+            }
+            score += 1;
+        }
+        let steps = self.backtrace(&wavefronts, score, k_end, n, m);
+        Alignment::new(Cursor { x: 0, y: 0 }, steps, -(score as isize))
+    }
+
+    // The wavefront of the current score from those `source(penalty)` below.
+    fn next_wavefront<'a, W>(source: &W, penalties: Penalties, n: isize, m: isize) -> Option<Front>
+    where
+        W: Fn(usize) -> Option<&'a Front>,
+    {
+        let Penalties {
+            mismatch,
+            delete: (delete_open, delete_extend),
+            insert: (insert_open, insert_extend),
+        } = penalties;
+        let mismatch = source(mismatch);
+        let (delete_open, delete_extend) =
+            (source(delete_open + delete_extend), source(delete_extend));
+        let (insert_open, insert_extend) =
+            (source(insert_open + insert_extend), source(insert_extend));
+        let sources = [
+            mismatch,
+            delete_open,
+            delete_extend,
+            insert_open,
+            insert_extend,
+        ];
+        let lo = sources.iter().flatten().map(|w| w.lo).min()? - 1;
+        let hi = sources.iter().flatten().map(|w| w.hi).max()? + 1;
+        let mut wavefront = Front::new(cmp::max(lo, -m), cmp::min(hi, n));
+        let get = |source: Option<&Front>, state: StepMask, k: isize| {
+            source.map_or(NONE, |w| w.get(state, k))
+        };
+        for k in wavefront.lo..=wavefront.hi {
+            let i = (k - wavefront.lo) as usize;
+            let insert = cmp::max(
+                get(insert_open, StepMask::ALIGN, k + 1),
+                get(insert_extend, StepMask::INSERT, k + 1),
+            );
+            let delete = cmp::max(
+                get(delete_open, StepMask::ALIGN, k - 1),
+                get(delete_extend, StepMask::DELETE, k - 1),
+            ) + 1;
+            let substitute = get(mismatch, StepMask::ALIGN, k) + 1;
+            let insert = valid(insert, k, n, m);
+            let delete = valid(delete, k, n, m);
+            let substitute = valid(substitute, k, n, m);
+            wavefront.insert[i] = insert;
+            wavefront.delete[i] = delete;
+            wavefront.align[i] = cmp::max(cmp::max(substitute, insert), delete);
+        }
+        Some(wavefront)
+    }
+
+    fn backtrace(
+        &self,
+        wavefronts: &[Option<Front>],
+        score: usize,
+        k_end: isize,
+        n: isize,
+        m: isize,
+    ) -> Vec<StepMask> {
+        let Penalties {
+            mismatch,
+            delete: (delete_open, delete_extend),
+            insert: (insert_open, insert_extend),
+        } = self.penalties();
+        let at = |score: usize, penalty: usize, state: StepMask, k: isize| {
+            if score < penalty {
+                return NONE;
+            }
+            wavefronts[score - penalty]
+                .as_ref()
+                .map_or(NONE, |w| w.get(state, k))
+        };
+        let mut steps = vec![];
+        let (mut score, mut k, mut x, mut state) = (score, k_end, n, StepMask::ALIGN);
+        loop {
+            match state {
+                StepMask::ALIGN => {
+                    if score == 0 {
+                        steps.extend((0..x).map(|_| StepMask::ALIGN));
+                        break;
+                    }
+                    let substitute = valid(at(score, mismatch, StepMask::ALIGN, k) + 1, k, n, m);
+                    let insert = at(score, 0, StepMask::INSERT, k);
+                    let delete = at(score, 0, StepMask::DELETE, k);
+                    let origin = cmp::max(cmp::max(substitute, insert), delete);
+                    steps.extend((origin..x).map(|_| StepMask::ALIGN));
+                    x = origin;
+                    if origin == substitute {
+                        steps.push(StepMask::ALIGN);
+                        score -= mismatch;
+                        x -= 1;
+                    } else if origin == delete {
+                        state = StepMask::DELETE;
+                    } else {
+                        state = StepMask::INSERT;
+                    }
+                }
+                StepMask::DELETE => {
+                    steps.push(StepMask::DELETE);
+                    let opened = at(score, delete_open + delete_extend, StepMask::ALIGN, k - 1) + 1;
+                    state = if opened == x {
+                        score -= delete_open + delete_extend;
+                        StepMask::ALIGN
+                    } else {
+                        score -= delete_extend;
+                        StepMask::DELETE
+                    };
+                    k -= 1;
+                    x -= 1;
+                }
+                _ => {
+                    steps.push(StepMask::INSERT);
+                    let opened = at(score, insert_open + insert_extend, StepMask::ALIGN, k + 1);
+                    state = if opened == x {
+                        score -= insert_open + insert_extend;
+                        StepMask::ALIGN
+                    } else {
+                        score -= insert_extend;
+                        StepMask::INSERT
+                    };
+                    k += 1;
+                }
+            }
+        }
+        steps.reverse();
+        steps
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pair::affine_alignment_set::AffineAlignmentSet;
+    use crate::pair::alignment_matrix::in_memory::AlignmentMatrix as InMemoryAlignmentMatrix;
+    use crate::pair::gap_models::AffineGaps;
+    use crate::pair::gotoh::Gotoh;
+    use crate::pair::step::Step;
+
+    fn check(str_x: &str, str_y: &str) {
+        check_with(Gotoh::new(0, -4, -6, -2), str_x, str_y);
+    }
+
+    fn check_with(strategy: Gotoh, str_x: &str, str_y: &str) {
+        let x: Vec<char> = str_x.chars().collect();
+        let y: Vec<char> = str_y.chars().collect();
+        let gaps = |mask| match (mask, strategy.insert) {
+            (StepMask::INSERT, Some(insert)) => insert,
+            _ => strategy.gaps,
+        };
+        let wavefront = Wavefront::new(strategy.clone()).unwrap();
+        let alignment = wavefront.global_alignment(x.len(), y.len(), |i, j| x[i] == y[j]);
+        let set: AffineAlignmentSet<InMemoryAlignmentMatrix> =
+            AffineAlignmentSet::new(x.len(), y.len(), strategy.clone(), |i, j| x[i] == y[j])
+                .unwrap();
+        assert_eq!(alignment.score(), set.global_score());
+        let mut penalty = 0;
+        let mut previous = StepMask::ALIGN;
+        for step in alignment.steps() {
+            penalty += match step {
+                Step::Align { x: i, y: j } if x[i] == y[j] => 0,
+                Step::Align { .. } => -strategy.align,
+                _ if step.mask() == previous => -gaps(step.mask()).extend,
+                _ => -gaps(step.mask()).open - gaps(step.mask()).extend,
+            };
+            previous = step.mask();
+        }
+        assert_eq!(-penalty, alignment.score());
+        assert_eq!(
+            alignment.end(),
+            Cursor {
+                x: x.len(),
+                y: y.len()
+            }
+        );
+    }
+
+    #[test]
+    fn global_alignment_works() {
+        check("GATTACA", "GATTACA");
+        check("GATTACA", "GAATTCAGTTA");
+        check(
+            "The quick brown fox jumps over the lazy dog.",
+            "The brown dog jumps over the very lazy snail.",
+        );
+    }
+
+    #[test]
+    fn invalid_scores_are_rejected() {
+        let result = Wavefront::new(Gotoh::new(1, -4, -6, -2));
+        assert_eq!(result.err(), Some(WavefrontError::MatchScore { score: 1 }));
+        let result = Wavefront::new(Gotoh::new(0, -4, -6, 0));
+        assert_eq!(
+            result.err(),
+            Some(WavefrontError::GapExtendScore { score: 0 })
+        );
+    }
+
+    #[test]
+    fn asymmetric_gaps_work() {
+        let strategy = Gotoh::new(0, -4, -6, -2).with_insert_gaps(AffineGaps {
+            open: -1,
+            extend: -1,
+        });
+        check_with(strategy.clone(), "GATTACA", "GAATTCAGTTA");
+        check_with(strategy.clone(), "GAATTCAGTTA", "GATTACA");
+        check_with(
+            strategy,
+            "The quick brown fox jumps over the lazy dog.",
+            "The brown dog jumps over the very lazy snail.",
+        );
+    }
+
+    #[test]
+    fn empty_works() {
+        check("", "ABC");
+        check("ABC", "");
+        check("", "");
+    }
+}
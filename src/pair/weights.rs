@@ -0,0 +1,77 @@
+use std::ops::Range;
+
+use crate::pair::strategy::Strategy;
+
+// Integer weights per position of x and y scaling the match and mismatch
+// scores, so that important regions dominate the alignment. Gap scores are
+// not weighted.
+#[derive(Clone, Debug)]
+pub struct Weights {
+    x: Vec<isize>,
+    y: Vec<isize>,
+}
+
+impl Weights {
+    pub fn new(x: Vec<isize>, y: Vec<isize>) -> Weights {
+        Weights { x, y }
+    }
+
+    // All positions get weight one.
+    pub fn uniform(x_len: usize, y_len: usize) -> Weights {
+        Weights::new(vec![1; x_len], vec![1; y_len])
+    }
+
+    pub fn set_x(&mut self, range: Range<usize>, weight: isize) {
+        self.x[range].iter_mut().for_each(|w| *w = weight);
+    }
+
+    pub fn set_y(&mut self, range: Range<usize>, weight: isize) {
+        self.y[range].iter_mut().for_each(|w| *w = weight);
+    }
+
+    // Scales the strategy's score for x[i] and y[j] by the weights of both
+    // positions.
+    pub fn scores<'a, S, F>(&'a self, strategy: &'a S, f: F) -> impl Fn(usize, usize) -> isize + 'a
+    where
+        S: Strategy,
+        F: Fn(usize, usize) -> bool + 'a,
+    {
+        move |i, j| {
+            let score = if f(i, j) {
+                strategy.match_score()
+            } else {
+                strategy.mismatch_score()
+            };
+            score * self.x[i] * self.y[j]
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pair::alignment_matrix::in_memory::AlignmentMatrix as InMemoryAlignmentMatrix;
+    use crate::pair::alignment_set::AlignmentSet;
+    use crate::pair::cursor::Cursor;
+    use crate::pair::smith_waterman::SmithWaterman;
+
+    #[test]
+    fn weighted_regions_dominate() {
+        let x = b"AAAAXXCGT";
+        let y = b"AAAACGT";
+        let strategy = SmithWaterman::new(1, -1, -1, -1);
+        let mut weights = Weights::uniform(x.len(), y.len());
+        let set: AlignmentSet<InMemoryAlignmentMatrix> =
+            AlignmentSet::builder(x.len(), y.len(), strategy.clone())
+                .build_with_scores(weights.scores(&strategy, |i, j| x[i] == y[j]))
+                .unwrap();
+        assert_eq!(set.local_alignment().origin(), &Cursor { x: 0, y: 0 });
+
+        weights.set_x(6..9, 3);
+        let set: AlignmentSet<InMemoryAlignmentMatrix> =
+            AlignmentSet::builder(x.len(), y.len(), strategy.clone())
+                .build_with_scores(weights.scores(&strategy, |i, j| x[i] == y[j]))
+                .unwrap();
+        assert_eq!(set.local_score(), 9 + 4 - 2);
+    }
+}
@@ -0,0 +1,47 @@
+// Symbols matching any other symbol, like `N` in nucleotide sequences.
+#[derive(Clone, Debug, Default)]
+pub struct Wildcards<T> {
+    symbols: Vec<T>,
+}
+
+impl<T> Wildcards<T>
+where
+    T: PartialEq,
+{
+    pub fn new(symbols: Vec<T>) -> Wildcards<T> {
+        Wildcards { symbols }
+    }
+
+    pub fn is_wildcard(&self, symbol: &T) -> bool {
+        self.symbols.contains(symbol)
+    }
+
+    pub fn matches(&self, a: &T, b: &T) -> bool {
+        a == b || self.is_wildcard(a) || self.is_wildcard(b)
+    }
+
+    // Whether x[i] equals y[j] or either of them is a wildcard.
+    pub fn matcher<'a>(&'a self, x: &'a [T], y: &'a [T]) -> impl Fn(usize, usize) -> bool + 'a {
+        move |i, j| self.matches(&x[i], &y[j])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pair::alignment_matrix::in_memory::AlignmentMatrix as InMemoryAlignmentMatrix;
+    use crate::pair::alignment_set::AlignmentSet;
+    use crate::pair::needleman_wunsch::NeedlemanWunsch;
+
+    #[test]
+    fn wildcards_match_anything() {
+        let x = b"ACGTNNGT";
+        let y = b"ACGTACG?";
+        let strategy = NeedlemanWunsch::new(1, -1, -1, -1);
+        let wildcards = Wildcards::new(vec![b'N', b'?']);
+        let set: AlignmentSet<InMemoryAlignmentMatrix> =
+            AlignmentSet::new(x.len(), y.len(), strategy, wildcards.matcher(x, y)).unwrap();
+        assert_eq!(set.global_score(), 8);
+        assert!(!wildcards.matches(&b'A', &b'C'));
+    }
+}
@@ -0,0 +1,238 @@
+use std::cmp;
+
+use crate::pair::alignment::Alignment;
+use crate::pair::alignment_set::MIN_SCORE;
+use crate::pair::alignments::Preference;
+use crate::pair::cursor::Cursor;
+use crate::pair::step_mask::StepMask;
+use crate::pair::strategy::Strategy;
+
+// Only the cells of a row that survived pruning are stored.
+struct Row {
+    start: usize,
+    steps: Vec<StepMask>,
+}
+
+#[derive(Clone, Debug)]
+pub struct XDrop<S> {
+    strategy: S,
+    x_drop: isize,
+    preference: Preference,
+}
+
+impl<S> XDrop<S>
+where
+    S: Strategy,
+{
+    pub fn new(strategy: S, x_drop: isize) -> XDrop<S> {
+        XDrop {
+            strategy,
+            x_drop,
+            preference: Preference::default(),
+        }
+    }
+
+    // Co-optimal steps are followed in the order of `preference`, in both
+    // directions from the seed.
+    pub fn with_preference(mut self, preference: Preference) -> XDrop<S> {
+        self.preference = preference;
+        self
+    }
+
+    // Extends from `seed` towards both ends of the sequences, stopping
+    // in each direction once all scores drop more than `x_drop` below
+    // the best score seen.
+    pub fn extend<F>(&self, x_len: usize, y_len: usize, seed: Cursor, f: F) -> Alignment
+    where
+        F: Fn(usize, usize) -> bool,
+    {
+        let backward_preference = self.preference.reversed();
+        let (backward_steps, backward_score, backward_end) =
+            self.extend_forwards(seed.x, seed.y, &backward_preference, |x, y| {
+                f(seed.x - 1 - x, seed.y - 1 - y)
+            });
+        let (forward_steps, forward_score, _) =
+            self.extend_forwards(x_len - seed.x, y_len - seed.y, &self.preference, |x, y| {
+                f(seed.x + x, seed.y + y)
+            });
+        let origin = Cursor {
+            x: seed.x - backward_end.x,
+            y: seed.y - backward_end.y,
+        };
+        let mut steps = backward_steps;
+        steps.reverse();
+        steps.extend(forward_steps);
+        Alignment::new(origin, steps, backward_score + forward_score)
+    }
+
+    fn extend_forwards<F>(
+        &self,
+        x_len: usize,
+        y_len: usize,
+        preference: &Preference,
+        f: F,
+    ) -> (Vec<StepMask>, isize, Cursor)
+    where
+        F: Fn(usize, usize) -> bool,
+    {
+        let strategy = &self.strategy;
+        let mut best = (0, Cursor { x: 0, y: 0 });
+        let mut rows = vec![];
+
+        let mut scores = vec![0];
+        let mut steps = vec![StepMask::STOP];
+        for x in 1..=x_len {
+            let score = scores[x - 1] + strategy.delete_score();
+            if score < best.0 - self.x_drop {
+                break;
+            }
+            scores.push(score);
+            steps.push(StepMask::DELETE);
+        }
+        rows.push(Row { start: 0, steps });
+        let mut start = 0;
+
+        for y in 1..=y_len {
+            let previous = |x: usize| {
+                if x >= start && x < start + scores.len() {
+                    scores[x - start]
+                } else {
+                    MIN_SCORE
+                }
+            };
+            let end = start + scores.len();
+            let mut row_scores = vec![];
+            let mut row_steps = vec![];
+            let mut row_start = None;
+            let mut left = MIN_SCORE;
+            for x in start..=x_len {
+                let align = if x > 0 {
+                    let equal = f(x - 1, y - 1);
+                    previous(x - 1)
+                        + if equal {
+                            strategy.match_score()
+                        } else {
+                            strategy.mismatch_score()
+                        }
+                } else {
+                    MIN_SCORE
+                };
+                let delete = left + strategy.delete_score();
+                let insert = previous(x) + strategy.insert_score();
+                let score = cmp::max(cmp::max(align, delete), insert);
+                let alive = score >= best.0 - self.x_drop;
+                if !alive && x > end {
+                    break;
+                }
+                left = if alive { score } else { MIN_SCORE };
+                if alive {
+                    if score > best.0 {
+                        best = (score, Cursor { x, y });
+                    }
+                    row_start.get_or_insert(x);
+                }
+                if row_start.is_some() {
+                    row_scores.push(left);
+                    row_steps.push(if alive {
+                        StepMask::from_scores(align, delete, insert)
+                    } else {
+                        StepMask::STOP
+                    });
+                }
+            }
+            while row_steps.last() == Some(&StepMask::STOP) {
+                row_scores.pop();
+                row_steps.pop();
+            }
+            match row_start {
+                Some(row_start) => {
+                    start = row_start;
+                    scores = row_scores;
+                    rows.push(Row {
+                        start,
+                        steps: row_steps,
+                    });
+                }
+                None => break,
+            }
+        }
+
+        let (score, end) = best;
+        let mut cursor = end;
+        let mut steps = vec![];
+        loop {
+            let row = &rows[cursor.y];
+            let mask = row.steps[cursor.x - row.start];
+            let step = match preference.first(mask) {
+                Some(step) => step,
+                None => break,
+            };
+            steps.push(step);
+            cursor.apply_backwards_step(step);
+        }
+        steps.reverse();
+        (steps, score, end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pair::needleman_wunsch::NeedlemanWunsch;
+
+    fn extend(x: &str, y: &str, seed: Cursor) -> Alignment {
+        let x: Vec<char> = x.chars().collect();
+        let y: Vec<char> = y.chars().collect();
+        let x_drop = XDrop::new(NeedlemanWunsch::new(1, -1, -1, -1), 3);
+        x_drop.extend(x.len(), y.len(), seed, |i, j| x[i] == y[j])
+    }
+
+    #[test]
+    fn extension_works() {
+        let alignment = extend(
+            "GGGGGACGTACGTTTTTT",
+            "CCCCACGTACGTAAAA",
+            Cursor { x: 8, y: 7 },
+        );
+        assert_eq!(alignment.score(), 8);
+        assert_eq!(alignment.origin(), &Cursor { x: 5, y: 4 });
+        assert_eq!(alignment.end(), Cursor { x: 13, y: 12 });
+    }
+
+    #[test]
+    fn extension_bridges_gaps() {
+        let alignment = extend(
+            "ACGTACGTXACGTACGT",
+            "ACGTACGTACGTACGT",
+            Cursor { x: 0, y: 0 },
+        );
+        assert_eq!(alignment.score(), 15);
+        assert_eq!(alignment.end(), Cursor { x: 17, y: 16 });
+    }
+
+    #[test]
+    fn extension_stops_at_ends() {
+        let alignment = extend("ACGT", "ACGT", Cursor { x: 2, y: 2 });
+        assert_eq!(alignment.score(), 4);
+        assert_eq!(alignment.origin(), &Cursor { x: 0, y: 0 });
+        assert_eq!(alignment.len(), 4);
+    }
+
+    #[test]
+    fn preference_places_gaps_in_both_directions() {
+        let x = b"ACGTTACGT";
+        let y = b"ACGTACGT";
+        let gap = |preference, seed| {
+            let x_drop =
+                XDrop::new(NeedlemanWunsch::new(1, -1, -1, -1), 3).with_preference(preference);
+            let alignment = x_drop.extend(x.len(), y.len(), seed, |i, j| x[i] == y[j]);
+            alignment
+                .steps()
+                .position(|step| step.mask() == StepMask::DELETE)
+        };
+        for seed in [Cursor { x: 0, y: 0 }, Cursor { x: 9, y: 8 }] {
+            assert_eq!(gap(Preference::GAPS_RIGHT, seed), Some(4));
+            assert_eq!(gap(Preference::GAPS_LEFT, seed), Some(3));
+        }
+    }
+}
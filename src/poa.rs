@@ -0,0 +1,250 @@
+use std::cmp;
+
+use crate::pair::step_mask::StepMask;
+use crate::pair::Strategy;
+
+#[derive(Clone, Debug)]
+struct Node<T> {
+    symbol: T,
+    predecessors: Vec<usize>,
+    // Successor nodes together with the number of sequences using the edge.
+    successors: Vec<(usize, usize)>,
+}
+
+// Pairs of graph node and sequence position, `None` marking a gap.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PoaAlignment {
+    pub score: isize,
+    pub pairs: Vec<(Option<usize>, Option<usize>)>,
+}
+
+// A partial order graph of sequences. Graph nodes take the role of x and
+// the aligned sequence that of y, so deleting skips a node.
+#[derive(Clone, Debug)]
+pub struct PoaGraph<T> {
+    nodes: Vec<Node<T>>,
+}
+
+impl<T> PoaGraph<T>
+where
+    T: Clone + PartialEq,
+{
+    pub fn new(sequence: &[T]) -> PoaGraph<T> {
+        let mut graph = PoaGraph { nodes: vec![] };
+        let pairs = (0..sequence.len()).map(|y| (None, Some(y))).collect();
+        graph.fold(sequence, &PoaAlignment { score: 0, pairs });
+        graph
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn symbol(&self, node: usize) -> &T {
+        &self.nodes[node].symbol
+    }
+
+    fn topological_order(&self) -> Vec<usize> {
+        let mut missing: Vec<usize> = self.nodes.iter().map(|n| n.predecessors.len()).collect();
+        let mut ready: Vec<usize> = (0..self.nodes.len()).filter(|&v| missing[v] == 0).collect();
+        let mut order = vec![];
+        while let Some(v) = ready.pop() {
+            order.push(v);
+            for &(w, _) in self.nodes[v].successors.iter() {
+                missing[w] -= 1;
+                if missing[w] == 0 {
+                    ready.push(w);
+                }
+            }
+        }
+        order
+    }
+
+    // Global alignment of `sequence` against any path from a source to a sink.
+    pub fn align<S: Strategy>(&self, sequence: &[T], strategy: &S) -> PoaAlignment {
+        let order = self.topological_order();
+        let mut rank = vec![0; self.nodes.len()];
+        for (i, &v) in order.iter().enumerate() {
+            rank[v] = i + 1;
+        }
+        let height = sequence.len() + 1;
+        // Row zero is a virtual start node preceding all sources.
+        let mut scores: Vec<Vec<isize>> = vec![vec![0; height]; order.len() + 1];
+        let mut steps = vec![vec![(StepMask::STOP, 0); height]; order.len() + 1];
+        for y in 1..height {
+            scores[0][y] = scores[0][y - 1].saturating_add(strategy.insert_score());
+            steps[0][y] = (StepMask::INSERT, 0);
+        }
+        for (i, &v) in order.iter().enumerate() {
+            let row = i + 1;
+            let node = &self.nodes[v];
+            let predecessors: Vec<usize> = if node.predecessors.is_empty() {
+                vec![0]
+            } else {
+                node.predecessors.iter().map(|&p| rank[p]).collect()
+            };
+            for y in 0..height {
+                let mut best = (isize::MIN, (StepMask::STOP, 0));
+                for &p in predecessors.iter() {
+                    if y > 0 {
+                        let substitution = if node.symbol == sequence[y - 1] {
+                            strategy.match_score()
+                        } else {
+                            strategy.mismatch_score()
+                        };
+                        let score = scores[p][y - 1].saturating_add(substitution);
+                        if score > best.0 {
+                            best = (score, (StepMask::ALIGN, p));
+                        }
+                    }
+                    let score = scores[p][y].saturating_add(strategy.delete_score());
+                    if score > best.0 {
+                        best = (score, (StepMask::DELETE, p));
+                    }
+                }
+                if y > 0 {
+                    let score = scores[row][y - 1].saturating_add(strategy.insert_score());
+                    if score > best.0 {
+                        best = (score, (StepMask::INSERT, row));
+                    }
+                }
+                scores[row][y] = best.0;
+                steps[row][y] = best.1;
+            }
+        }
+
+        let sinks = order
+            .iter()
+            .filter(|&&v| self.nodes[v].successors.is_empty())
+            .map(|&v| rank[v]);
+        let mut row = sinks
+            .max_by_key(|&row| (scores[row][height - 1], cmp::Reverse(row)))
+            .unwrap_or(0);
+        let score = scores[row][height - 1];
+        let mut y = height - 1;
+        let mut pairs = vec![];
+        loop {
+            let (step, previous) = steps[row][y];
+            let node = if row > 0 { Some(order[row - 1]) } else { None };
+            match step {
+                StepMask::ALIGN => {
+                    y -= 1;
+                    pairs.push((node, Some(y)));
+                }
+                StepMask::DELETE => pairs.push((node, None)),
+                StepMask::INSERT => {
+                    y -= 1;
+                    pairs.push((None, Some(y)));
+                }
+                _ => break,
+            }
+            row = previous;
+        }
+        pairs.reverse();
+        PoaAlignment { score, pairs }
+    }
+
+    pub fn add<S: Strategy>(&mut self, sequence: &[T], strategy: &S) {
+        let alignment = self.align(sequence, strategy);
+        self.fold(sequence, &alignment);
+    }
+
+    // Matching aligned nodes get reused, all other symbols become new nodes.
+    fn fold(&mut self, sequence: &[T], alignment: &PoaAlignment) {
+        let mut last: Option<usize> = None;
+        for &(node, y) in alignment.pairs.iter() {
+            let y = match y {
+                Some(y) => y,
+                None => continue,
+            };
+            let node = match node {
+                Some(node) if self.nodes[node].symbol == sequence[y] => node,
+                _ => {
+                    self.nodes.push(Node {
+                        symbol: sequence[y].clone(),
+                        predecessors: vec![],
+                        successors: vec![],
+                    });
+                    self.nodes.len() - 1
+                }
+            };
+            if let Some(last) = last {
+                self.add_edge(last, node);
+            }
+            last = Some(node);
+        }
+    }
+
+    fn add_edge(&mut self, from: usize, to: usize) {
+        let successors = &mut self.nodes[from].successors;
+        match successors.iter_mut().find(|(w, _)| *w == to) {
+            Some(edge) => edge.1 += 1,
+            None => {
+                successors.push((to, 1));
+                self.nodes[to].predecessors.push(from);
+            }
+        }
+    }
+
+    // The path with the highest summed edge support.
+    pub fn consensus(&self) -> Vec<T> {
+        let mut best: Vec<(usize, Option<usize>)> = vec![(0, None); self.nodes.len()];
+        for v in self.topological_order() {
+            for &(w, weight) in self.nodes[v].successors.iter() {
+                if best[w].1.is_none() || best[v].0 + weight > best[w].0 {
+                    best[w] = (best[v].0 + weight, Some(v));
+                }
+            }
+        }
+        let mut node = (0..self.nodes.len()).max_by_key(|&v| (best[v].0, cmp::Reverse(v)));
+        let mut consensus = vec![];
+        while let Some(v) = node {
+            consensus.push(self.nodes[v].symbol.clone());
+            node = best[v].1;
+        }
+        consensus.reverse();
+        consensus
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pair::NeedlemanWunsch;
+
+    fn bytes(string: &str) -> Vec<u8> {
+        string.bytes().collect()
+    }
+
+    #[test]
+    fn align_works() {
+        let graph = PoaGraph::new(&bytes("GATTACA"));
+        let strategy = NeedlemanWunsch::new(1, -1, -1, -1);
+        let alignment = graph.align(&bytes("GATACA"), &strategy);
+        assert_eq!(alignment.score, 5);
+        assert_eq!(alignment.pairs.len(), 7);
+    }
+
+    #[test]
+    fn consensus_works() {
+        let strategy = NeedlemanWunsch::new(1, -1, -1, -1);
+        let mut graph = PoaGraph::new(&bytes("GATTACA"));
+        graph.add(&bytes("GATACA"), &strategy);
+        graph.add(&bytes("GATTACA"), &strategy);
+        graph.add(&bytes("GCTTACA"), &strategy);
+        assert_eq!(graph.consensus(), bytes("GATTACA"));
+        // Aligning against the merged graph may follow any of its paths.
+        assert_eq!(graph.align(&bytes("GCTTACA"), &strategy).score, 7);
+    }
+
+    #[test]
+    fn huge_gap_scores_saturate() {
+        let graph = PoaGraph::new(&bytes("GATTACA"));
+        let strategy = NeedlemanWunsch::new(1, -1, isize::MIN / 2, isize::MIN / 2);
+        assert_eq!(graph.align(&bytes("GATTACA"), &strategy).score, 7);
+    }
+}